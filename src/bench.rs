@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use crate::registry::{Solver, Timing};
+use crate::solution::{Answer, Options, Part, SolveError};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Summary {
+    // nearest-rank percentiles, `samples` must not be empty
+    pub fn new(samples: &mut [Duration]) -> Summary {
+        samples.sort();
+        let rank = |percentile: usize| (samples.len() * percentile).div_ceil(100).max(1) - 1;
+        return Summary {
+            min: samples[0],
+            median: samples[rank(50)],
+            p95: samples[rank(95)],
+        };
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let min = format_duration(self.min);
+        let median = format_duration(self.median);
+        let p95 = format_duration(self.p95);
+        write!(f, "{min:>8} {median:>8} {p95:>8}")
+    }
+}
+
+pub struct Benchmark {
+    // None for solvers parsing while solving
+    pub parse: Option<Summary>,
+    pub solve: Summary,
+    pub total: Summary,
+}
+
+// runs the solver `runs` times, the answer is the one of the last run
+pub fn bench(
+    solver: &Solver,
+    input: &str,
+    part: Part,
+    options: &Options,
+    runs: usize,
+) -> Result<(Answer, Benchmark), SolveError> {
+    assert!(runs > 0);
+    let mut timings: Vec<Timing> = Vec::with_capacity(runs);
+    let mut answer = None;
+    for _ in 0..runs {
+        let (run_answer, timing) = (solver.solve)(input, part, options)?;
+        answer = Some(run_answer);
+        timings.push(timing);
+    }
+    let summarize = |duration: fn(&Timing) -> Duration| {
+        let mut samples: Vec<Duration> = timings.iter().map(duration).collect();
+        Summary::new(&mut samples)
+    };
+    let benchmark = Benchmark {
+        parse: timings[0].parse.map(|_| summarize(|timing| timing.parse.unwrap())),
+        solve: summarize(|timing| timing.solve),
+        total: summarize(|timing| timing.total()),
+    };
+    return Ok((answer.unwrap(), benchmark));
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    return if nanos < 1e3 {
+        format!("{nanos:.0}ns")
+    } else if nanos < 1e6 {
+        format!("{:.1}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.1}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_uses_nearest_rank() {
+        let mut samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let summary = Summary::new(&mut samples);
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.median, Duration::from_millis(10));
+        assert_eq!(summary.p95, Duration::from_millis(19));
+
+        let summary = Summary::new(&mut [Duration::from_micros(1500)]);
+        assert_eq!(summary.p95, Duration::from_micros(1500));
+        assert_eq!(summary.to_string(), "   1.5ms    1.5ms    1.5ms");
+    }
+}
@@ -0,0 +1,60 @@
+pub fn get_c(n: usize, k: usize) -> i128 {
+    let mut result: i128 = 1;
+    for i in n - k + 1..n + 1 {
+        result *= i as i128;
+    }
+    for i in 1..k + 1 {
+        result /= i as i128;
+    }
+    return result;
+}
+
+pub fn gcd(a: i128, b: i128) -> i128 {
+    return if b == 0 { a.abs() } else { gcd(b, a % b) };
+}
+
+pub fn lcm(a: i128, b: i128) -> i128 {
+    return a / gcd(a, b) * b;
+}
+
+// (g, x, y) with a * x + b * y = g = gcd(a, b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    return (g, y, x - a / b * y);
+}
+
+// generalized chinese remainder theorem, moduli don't need to be coprime:
+// the x in [0, lcm(m, n)) with x = a mod m and x = b mod n, if there is one
+pub fn crt(a: i128, m: i128, b: i128, n: i128) -> Option<i128> {
+    let (g, inverse, _) = extended_gcd(m, n);
+    if (b - a) % g != 0 {
+        return None;
+    }
+    let l = m / g * n;
+    let k = ((b - a) / g % (n / g) * inverse).rem_euclid(n / g);
+    return Some((a + m * k).rem_euclid(l));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crt_handles_moduli_sharing_factors() {
+        assert_eq!(crt(2, 3, 3, 5), Some(8));
+        // gcd(6, 4) = 2, residues agreeing mod 2 have a solution mod 12
+        assert_eq!(crt(1, 6, 3, 4), Some(7));
+        assert_eq!(crt(0, 6, 2, 4), Some(6));
+        assert_eq!(crt(1, 6, 2, 4), None);
+        assert_eq!(crt(5, 10, 5, 10), Some(5));
+        assert_eq!(crt(3, 4, 1, 2), Some(3));
+        assert_eq!(lcm(6, 4), 12);
+        // products of the moduli don't overflow
+        let big: i128 = 1_000_000_007 * 998_244_353;
+        let x = crt(big - 1, big, 5, 6).unwrap();
+        assert!(x < big * 6 && x % big == big - 1 && x % 6 == 5);
+    }
+}
@@ -0,0 +1,51 @@
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub const DIRECTIONS_4: &[Point] = &[
+    Point { x: 1, y: 0 },
+    Point { x: 0, y: 1 },
+    Point { x: -1, y: 0 },
+    Point { x: 0, y: -1 },
+];
+pub const DIRECTIONS_6: &[Point] = &[
+    Point { x: 1, y: -1 },
+    Point { x: 1, y: 0 },
+    Point { x: 1, y: 1 },
+    Point { x: -1, y: -1 },
+    Point { x: -1, y: 0 },
+    Point { x: -1, y: 1 },
+];
+pub const DIRECTIONS_8: &[Point] = &[
+    Point { x: 1, y: 0 },
+    Point { x: 1, y: 1 },
+    Point { x: 0, y: 1 },
+    Point { x: -1, y: 1 },
+    Point { x: -1, y: 0 },
+    Point { x: -1, y: -1 },
+    Point { x: 0, y: -1 },
+    Point { x: 1, y: -1 },
+];
+
+impl Point {
+    pub fn mult(&self, k: i32) -> Point {
+        Point {
+            x: self.x * k,
+            y: self.y * k,
+        }
+    }
+    pub fn add(&self, other: Point) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+    pub fn sub(&self, other: Point) -> Point {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use crate::geometry::{Point, DIRECTIONS_4, DIRECTIONS_8};
+use crate::parsing::ParseError;
+
+// owned and mutable, row-major without separators, x is the row and y the column
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    pub cells: Vec<T>,
+    pub width: i32,
+    pub height: i32,
+    // what `at` returns out of bounds
+    pub outside: T,
+}
+
+impl<T: Clone> Grid<T> {
+    // `fill` is also the value outside
+    pub fn new(width: i32, height: i32, fill: T) -> Grid<T> {
+        assert!(width >= 0 && height >= 0);
+        return Grid {
+            cells: vec![fill.clone(); (width * height) as usize],
+            width,
+            height,
+            outside: fill,
+        };
+    }
+}
+
+// a row of another width than the first one, `line` is 1-based
+#[derive(PartialEq, Eq, Debug)]
+pub struct RaggedRow {
+    pub line: usize,
+    pub row: String,
+    pub width: usize,
+}
+
+impl RaggedRow {
+    // the row is the token, solvers attach their day
+    pub fn into_parse_error(self, day: u8) -> ParseError {
+        let expected = format!("{} cells", self.width);
+        return ParseError::new(day, self.line, 1, self.row.as_bytes(), &expected);
+    }
+}
+
+impl std::fmt::Display for RaggedRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (line, width, row) = (self.line, self.width, &self.row);
+        write!(f, "line {line}: expected {width} cells, found {row:?}")
+    }
+}
+
+impl Grid<u8> {
+    // the text form, one row per line ending with '\n' or "\r\n", '.' outside
+    pub fn parse(input: &str) -> Result<Grid<u8>, RaggedRow> {
+        let mut lines = input.trim_end_matches(['\r', '\n']).lines().peekable();
+        let width = lines.peek().map_or(0, |line| line.len());
+        let mut grid = Grid {
+            cells: Vec::with_capacity(input.len()),
+            width: width as i32,
+            height: 0,
+            outside: '.' as u8,
+        };
+        for (number, line) in lines.enumerate() {
+            if line.len() != width {
+                return Err(RaggedRow { line: number + 1, row: line.to_string(), width });
+            }
+            grid.cells.extend_from_slice(line.as_bytes());
+            grid.height += 1;
+        }
+        return Ok(grid);
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn pos(&self, Point { x: row, y: col }: Point) -> Option<usize> {
+        if row < 0 || col < 0 || row >= self.height || col >= self.width {
+            return None;
+        }
+        return Some((row * self.width + col) as usize);
+    }
+    pub fn index(&self, index: i32) -> Option<Point> {
+        if index < 0 || index >= self.cells.len() as i32 {
+            return None;
+        }
+        return Some(Point {
+            x: index / self.width,
+            y: index % self.width,
+        });
+    }
+    pub fn get(&self, position: Point) -> Option<&T> {
+        return self.pos(position).map(|pos| &self.cells[pos]);
+    }
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        return self.pos(position).map(|pos| &mut self.cells[pos]);
+    }
+    // the previous value, None and nothing changes out of bounds
+    pub fn set(&mut self, position: Point, value: T) -> Option<T> {
+        return self
+            .get_mut(position)
+            .map(|cell| std::mem::replace(cell, value));
+    }
+    pub fn row(&self, row: i32) -> &[T] {
+        let start = (row * self.width) as usize;
+        return &self.cells[start..start + self.width as usize];
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0
+        return self.cells.chunks(self.width.max(1) as usize);
+    }
+    pub fn column(&self, col: i32) -> impl Iterator<Item = &T> {
+        assert!(0 <= col && col < self.width);
+        return self.cells.iter().skip(col as usize).step_by(self.width as usize);
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        return (0..self.width).map(|col| self.column(col));
+    }
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        return (0..self.height).flat_map(move |x| (0..width).map(move |y| Point { x, y }));
+    }
+    // the cells at `offsets` from `position` that are in the grid
+    pub fn neighbours<'g>(
+        &'g self,
+        position: Point,
+        offsets: &'g [Point],
+    ) -> impl Iterator<Item = (Point, &'g T)> + 'g {
+        return offsets.iter().filter_map(move |&offset| {
+            let neighbour = position.add(offset);
+            return self.get(neighbour).map(|value| (neighbour, value));
+        });
+    }
+    pub fn neighbours_4(&self, position: Point) -> impl Iterator<Item = (Point, &T)> {
+        return self.neighbours(position, DIRECTIONS_4);
+    }
+    pub fn neighbours_8(&self, position: Point) -> impl Iterator<Item = (Point, &T)> {
+        return self.neighbours(position, DIRECTIONS_8);
+    }
+    // like `neighbours` with the edges glued together, as on a torus
+    pub fn wrapping_neighbours<'g>(
+        &'g self,
+        position: Point,
+        offsets: &'g [Point],
+    ) -> impl Iterator<Item = (Point, &'g T)> + 'g {
+        let empty = self.width == 0 || self.height == 0;
+        return offsets.iter().filter(move |_| !empty).map(move |&offset| {
+            let Point { x, y } = position.add(offset);
+            let neighbour = Point {
+                x: x.rem_euclid(self.height),
+                y: y.rem_euclid(self.width),
+            };
+            return (neighbour, &self.cells[self.pos(neighbour).unwrap()]);
+        });
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn at(&self, position: Point) -> T {
+        return self.get(position).copied().unwrap_or(self.outside);
+    }
+}
+
+// a rotated, transposed, flipped or cropped window on a Grid, without copying the cells:
+// view (row, col) is the cell `origin + row * row_step + col * col_step`
+pub struct GridView<'g, T> {
+    pub grid: &'g Grid<T>,
+    origin: i32,
+    row_step: i32,
+    col_step: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl<'g, T> Clone for GridView<'g, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'g, T> Copy for GridView<'g, T> {}
+
+impl<'g, T> GridView<'g, T> {
+    pub fn pos(&self, Point { x: row, y: col }: Point) -> Option<usize> {
+        if row < 0 || col < 0 || row >= self.height || col >= self.width {
+            return None;
+        }
+        return Some((self.origin + row * self.row_step + col * self.col_step) as usize);
+    }
+    pub fn get(&self, position: Point) -> Option<&'g T> {
+        let cells = &self.grid.cells;
+        return self.pos(position).map(|pos| &cells[pos]);
+    }
+    pub fn row(&self, row: i32) -> impl Iterator<Item = &'g T> {
+        let view = *self;
+        return (0..self.width).map(move |col| view.get(Point { x: row, y: col }).unwrap());
+    }
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &'g T>> {
+        let view = *self;
+        return (0..self.height).map(move |row| view.row(row));
+    }
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        return (0..self.height).flat_map(move |x| (0..width).map(move |y| Point { x, y }));
+    }
+    pub fn transpose(&self) -> GridView<'g, T> {
+        return GridView {
+            row_step: self.col_step,
+            col_step: self.row_step,
+            width: self.height,
+            height: self.width,
+            ..*self
+        };
+    }
+    // mirrors left and right
+    pub fn flip_horizontal(&self) -> GridView<'g, T> {
+        return GridView {
+            origin: self.origin + (self.width - 1) * self.col_step,
+            col_step: -self.col_step,
+            ..*self
+        };
+    }
+    // mirrors top and bottom
+    pub fn flip_vertical(&self) -> GridView<'g, T> {
+        return GridView {
+            origin: self.origin + (self.height - 1) * self.row_step,
+            row_step: -self.row_step,
+            ..*self
+        };
+    }
+    // clockwise
+    pub fn rotate(&self, quarter_turns: i32) -> GridView<'g, T> {
+        return match quarter_turns.rem_euclid(4) {
+            0 => *self,
+            1 => self.transpose().flip_horizontal(),
+            2 => self.flip_horizontal().flip_vertical(),
+            _ => self.transpose().flip_vertical(),
+        };
+    }
+    // `top_left` is in this view, the window has to fit in it
+    pub fn sub(&self, top_left: Point, width: i32, height: i32) -> GridView<'g, T> {
+        assert!(top_left.x >= 0 && top_left.y >= 0 && width >= 0 && height >= 0);
+        assert!(top_left.x + height <= self.height && top_left.y + width <= self.width);
+        return GridView {
+            origin: self.origin + top_left.x * self.row_step + top_left.y * self.col_step,
+            width,
+            height,
+            ..*self
+        };
+    }
+}
+
+impl<'g, T: Copy> GridView<'g, T> {
+    pub fn at(&self, position: Point) -> T {
+        return self.get(position).copied().unwrap_or(self.grid.outside);
+    }
+}
+
+impl<'g, T: Clone> GridView<'g, T> {
+    pub fn to_grid(&self) -> Grid<T> {
+        return Grid {
+            cells: self.rows().flatten().cloned().collect(),
+            width: self.width,
+            height: self.height,
+            outside: self.grid.outside.clone(),
+        };
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self) -> GridView<'_, T> {
+        return GridView {
+            grid: self,
+            origin: 0,
+            row_step: self.width,
+            col_step: 1,
+            width: self.width,
+            height: self.height,
+        };
+    }
+    pub fn transpose(&self) -> GridView<'_, T> {
+        return self.view().transpose();
+    }
+    pub fn rotate(&self, quarter_turns: i32) -> GridView<'_, T> {
+        return self.view().rotate(quarter_turns);
+    }
+    pub fn flip_horizontal(&self) -> GridView<'_, T> {
+        return self.view().flip_horizontal();
+    }
+    pub fn flip_vertical(&self) -> GridView<'_, T> {
+        return self.view().flip_vertical();
+    }
+    pub fn sub(&self, top_left: Point, width: i32, height: i32) -> GridView<'_, T> {
+        return self.view().sub(top_left, width, height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owned_grid_reads_and_writes() {
+        let mut grid = Grid::parse("#..\n.#.\n").unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.at(Point { x: 1, y: 1 }), b'#');
+        assert_eq!(grid.at(Point { x: 2, y: 0 }), b'.');
+        assert_eq!(grid.set(Point { x: 0, y: 2 }, b'#'), Some(b'.'));
+        assert_eq!(grid.set(Point { x: 0, y: 3 }, b'#'), None);
+        assert_eq!(grid.row(0), b"#.#");
+        let columns: Vec<Vec<u8>> = grid.columns().map(|col| col.copied().collect()).collect();
+        assert_eq!(columns, [b"#.", b".#", b"#."]);
+        assert_eq!(grid.index(4), Some(Point { x: 1, y: 1 }));
+        assert_eq!(grid.points().filter(|&p| grid.at(p) == b'#').count(), 3);
+
+        let mut counts = Grid::new(2, 3, 0);
+        *counts.get_mut(Point { x: 2, y: 1 }).unwrap() += 5;
+        let sums: Vec<i32> = counts.rows().map(|row| row.iter().sum()).collect();
+        assert_eq!(sums, [0, 0, 5]);
+        assert_eq!(counts.at(Point { x: -1, y: 0 }), 0);
+    }
+
+    #[test]
+    fn views_transform_without_copying() {
+        let grid = Grid::parse("abc\ndef\n").unwrap();
+        let text = |view: GridView<u8>| {
+            let rows: Vec<String> =
+                view.rows().map(|row| row.map(|&x| x as char).collect()).collect();
+            rows.join("/")
+        };
+        assert_eq!(text(grid.view()), "abc/def");
+        assert_eq!(text(grid.transpose()), "ad/be/cf");
+        assert_eq!(text(grid.rotate(1)), "da/eb/fc");
+        assert_eq!(text(grid.rotate(2)), "fed/cba");
+        assert_eq!(text(grid.rotate(-1)), "cf/be/ad");
+        assert_eq!(text(grid.flip_horizontal()), "cba/fed");
+        assert_eq!(text(grid.flip_vertical()), "def/abc");
+        assert_eq!(text(grid.rotate(1).rotate(3)), "abc/def");
+        let window = grid.rotate(1).sub(Point { x: 1, y: 0 }, 2, 2);
+        assert_eq!(text(window), "eb/fc");
+        assert_eq!(window.at(Point { x: 0, y: 2 }), b'.');
+        assert_eq!(window.to_grid(), Grid::parse("eb\nfc").unwrap());
+    }
+
+    #[test]
+    fn grids_parse_any_line_ending() {
+        let grid = Grid::parse("12\n34\n\n").unwrap();
+        assert_eq!((grid.width, grid.height, grid.cells.as_slice()), (2, 2, b"1234".as_slice()));
+        assert_eq!(Grid::parse("12\r\n34\r\n").unwrap(), grid);
+        assert_eq!(Grid::parse("12\n34").unwrap(), grid);
+        assert_eq!(Grid::parse("").unwrap().height, 0);
+        let error = Grid::parse("12\n345\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 2 cells, found \"345\"");
+        let error = error.into_parse_error(17);
+        assert_eq!((error.line, error.column, error.token.as_str()), (2, 1, "345"));
+        assert_eq!(error.to_string(), "day17 line 2 column 1: expected 2 cells, found \"345\"");
+        assert_eq!(Grid::parse("12\n\n34").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn neighbours_stay_in_the_grid_or_wrap() {
+        let grid = Grid::parse("abc\ndef\nghi").unwrap();
+        let values = |neighbours: Vec<(Point, &u8)>| -> String {
+            let mut values: Vec<char> = neighbours.iter().map(|(_, &x)| x as char).collect();
+            values.sort();
+            values.into_iter().collect()
+        };
+        let corner = Point { x: 0, y: 0 };
+        assert_eq!(values(grid.neighbours_4(corner).collect()), "bd");
+        assert_eq!(values(grid.neighbours_8(corner).collect()), "bde");
+        assert_eq!(values(grid.neighbours_8(Point { x: 1, y: 1 }).collect()), "abcdfghi");
+        let knight = [Point { x: 2, y: 1 }, Point { x: 1, y: 2 }, Point { x: -1, y: 2 }];
+        let jumps: Vec<(Point, &u8)> = grid.neighbours(corner, &knight).collect();
+        assert_eq!(jumps, [(Point { x: 2, y: 1 }, &b'h'), (Point { x: 1, y: 2 }, &b'f')]);
+        let wrapped: Vec<(Point, &u8)> = grid.wrapping_neighbours(corner, DIRECTIONS_4).collect();
+        assert_eq!(values(wrapped.clone()), "bcdg");
+        assert!(wrapped.contains(&(Point { x: 2, y: 0 }, &b'g')));
+        assert_eq!(Grid::new(0, 0, 0).wrapping_neighbours(corner, DIRECTIONS_8).count(), 0);
+    }
+}
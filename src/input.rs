@@ -0,0 +1,244 @@
+use crate::parsing::split_exact;
+use crate::solution::{Answer, Part};
+
+// fallback for inputs missing in the inputs directory
+#[rustfmt::skip]
+pub const EMBEDDED_INPUTS: &[(&str, &str)] = &[
+    ("input01", include_str!("inputs/input01.txt")),
+    ("input02", include_str!("inputs/input02.txt")),
+    ("input03", include_str!("inputs/input03.txt")),
+    ("input04", include_str!("inputs/input04.txt")),
+    ("input05", include_str!("inputs/input05.txt")),
+    ("input06", include_str!("inputs/input06.txt")),
+    ("input07", include_str!("inputs/input07.txt")),
+    ("input08", include_str!("inputs/input08.txt")),
+    ("input09", include_str!("inputs/input09.txt")),
+    ("input10", include_str!("inputs/input10.txt")),
+    ("input11", include_str!("inputs/input11.txt")),
+    ("input12", include_str!("inputs/input12.txt")),
+    ("input17", include_str!("inputs/input17.txt")),
+    ("input18", include_str!("inputs/input18.txt")),
+    ("example01", include_str!("inputs/example01.txt")),
+    ("example02", include_str!("inputs/example02.txt")),
+    ("example03", include_str!("inputs/example03.txt")),
+    ("example04", include_str!("inputs/example04.txt")),
+    ("example05", include_str!("inputs/example05.txt")),
+    ("example06", include_str!("inputs/example06.txt")),
+    ("example07", include_str!("inputs/example07.txt")),
+    ("example08", include_str!("inputs/example08.txt")),
+    ("example09", include_str!("inputs/example09.txt")),
+    ("example10", include_str!("inputs/example10.txt")),
+    ("example11", include_str!("inputs/example11.txt")),
+    ("example12", include_str!("inputs/example12.txt")),
+    ("example17", include_str!("inputs/example17.txt")),
+    ("example18", include_str!("inputs/example18.txt")),
+];
+pub const EMBEDDED_ANSWERS: &str = include_str!("inputs/answers.txt");
+
+pub struct RecordedAnswers {
+    entries: Vec<(String, Part, String)>,
+}
+
+impl RecordedAnswers {
+    pub fn parse(text: &str) -> Result<RecordedAnswers, String> {
+        let mut entries = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let [name, part, answer] = split_exact::<3>(line, ' ')
+                .map_err(|e| format!("answers line {}: {e}", i + 1))?;
+            let part =
+                Part::parse(part).ok_or_else(|| format!("answers line {}: invalid part", i + 1))?;
+            entries.push((name.to_string(), part, answer.to_string()));
+        }
+        return Ok(RecordedAnswers { entries });
+    }
+    pub fn get(&self, name: &str, part: Part) -> Option<&str> {
+        return self
+            .entries
+            .iter()
+            .find(|(entry_name, entry_part, _)| entry_name == name && *entry_part == part)
+            .map(|(_, _, answer)| answer.as_str());
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InputKind {
+    Input,
+    Example,
+}
+
+impl InputKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputKind::Input => "input",
+            InputKind::Example => "example",
+        }
+    }
+}
+
+pub enum InputSource {
+    Embedded,
+    // inputNN.txt / exampleNN.txt files, missing ones fall back to the embedded inputs
+    Directory(std::path::PathBuf),
+    File(std::path::PathBuf),
+    Stdin,
+}
+
+pub struct Input {
+    // name of a known input (`input03`, `example18`) which has recorded answers
+    pub name: Option<String>,
+    pub embedded: bool,
+    pub text: std::borrow::Cow<'static, str>,
+}
+
+pub struct Inputs {
+    source: InputSource,
+    stdin: Option<String>,
+    embedded_answers: RecordedAnswers,
+    directory_answers: Option<RecordedAnswers>,
+}
+
+impl Inputs {
+    pub fn new(source: InputSource) -> Result<Inputs, String> {
+        let embedded_answers = RecordedAnswers::parse(EMBEDDED_ANSWERS)?;
+        let mut directory_answers = None;
+        if let InputSource::Directory(directory) = &source {
+            // only the files missing from an existing directory fall back to the embedded ones
+            if !directory.is_dir() {
+                return Err(format!("{} is not a directory", directory.display()));
+            }
+            let path = directory.join("answers.txt");
+            if path.exists() {
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("can't read {}: {e}", path.display()))?;
+                directory_answers = Some(RecordedAnswers::parse(&text)?);
+            }
+        }
+        return Ok(Inputs {
+            source,
+            stdin: None,
+            embedded_answers,
+            directory_answers,
+        });
+    }
+    pub fn load(&mut self, day: u8, kind: InputKind) -> Result<Option<Input>, String> {
+        let name = format!("{}{day:02}", kind.name());
+        let embedded = |name: String| {
+            EMBEDDED_INPUTS
+                .iter()
+                .find(|(input_name, _)| *input_name == name)
+                .map(|(_, text)| Input {
+                    name: Some(name),
+                    embedded: true,
+                    text: std::borrow::Cow::Borrowed(*text),
+                })
+        };
+        let read = |path: &std::path::Path| {
+            std::fs::read_to_string(path)
+                .map(std::borrow::Cow::Owned)
+                .map_err(|e| format!("can't read {}: {e}", path.display()))
+        };
+        return match &self.source {
+            InputSource::Embedded => Ok(embedded(name)),
+            InputSource::Directory(directory) => {
+                let path = directory.join(format!("{name}.txt"));
+                if path.exists() {
+                    Ok(Some(Input {
+                        name: Some(name),
+                        embedded: false,
+                        text: read(&path)?,
+                    }))
+                } else {
+                    Ok(embedded(name))
+                }
+            }
+            InputSource::File(path) => Ok(Some(Input {
+                name: None,
+                embedded: false,
+                text: read(path)?,
+            })),
+            InputSource::Stdin => {
+                if self.stdin.is_none() {
+                    let mut input = String::new();
+                    std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+                        .map_err(|e| format!("can't read stdin: {e}"))?;
+                    self.stdin = Some(input);
+                }
+                Ok(Some(Input {
+                    name: None,
+                    embedded: false,
+                    text: std::borrow::Cow::Owned(self.stdin.clone().unwrap()),
+                }))
+            }
+        };
+    }
+    pub fn expected(&self, input: &Input, part: Part) -> Option<&str> {
+        let answers = if input.embedded {
+            Some(&self.embedded_answers)
+        } else {
+            self.directory_answers.as_ref()
+        };
+        return answers?.get(input.name.as_deref()?, part);
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Check {
+    // nothing recorded for this input
+    Unknown,
+    Pass,
+    Fail(String),
+    // approximate answer differs from the recorded one, which is expected to happen sometimes
+    Imprecise(String),
+}
+
+impl Check {
+    pub fn new(answer: Answer, expected: Option<&str>) -> Check {
+        let Some(expected) = expected else {
+            return Check::Unknown;
+        };
+        let actual = answer.to_string();
+        return if actual.trim_start_matches('~') == expected {
+            Check::Pass
+        } else if let Answer::Approx(_) = answer {
+            Check::Imprecise(expected.to_string())
+        } else {
+            Check::Fail(expected.to_string())
+        };
+    }
+}
+
+impl std::fmt::Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Unknown => Ok(()),
+            Check::Pass => write!(f, " [ok]"),
+            Check::Fail(expected) => write!(f, " [FAIL, expected {expected}]"),
+            Check::Imprecise(expected) => write!(f, " [imprecise, expected {expected}]"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_answers_parse() {
+        let answers = RecordedAnswers::parse(EMBEDDED_ANSWERS).unwrap();
+        assert_eq!(answers.get("example03", Part::Easy), Some("4361"));
+        assert_eq!(answers.get("input05", Part::Hard), Some("17729182"));
+        assert_eq!(answers.get("input13", Part::Easy), None);
+    }
+
+    #[test]
+    fn missing_input_directories_are_errors() {
+        let missing = InputSource::Directory("/nonexistent/aoc2023".into());
+        let error = Inputs::new(missing).err().unwrap();
+        assert_eq!(error, "/nonexistent/aoc2023 is not a directory");
+        assert!(Inputs::new(InputSource::Directory(std::env::temp_dir())).is_ok());
+    }
+}
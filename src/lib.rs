@@ -0,0 +1,11 @@
+pub mod bench;
+pub mod combinatorics;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parsing;
+pub mod registry;
+pub mod report;
+pub mod search;
+pub mod solution;
+pub mod solvers;
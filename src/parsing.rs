@@ -0,0 +1,163 @@
+#[derive(PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub day: u8,
+    // both 1-based, column counts bytes
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, token: &[u8], expected: &str) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column,
+            token: String::from_utf8_lossy(token).into_owned(),
+            expected: expected.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (day, line, column, expected) = (self.day, self.line, self.column, &self.expected);
+        write!(f, "day{day:02} line {line} column {column}: expected {expected}, ")?;
+        if self.token.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found {:?}", self.token)
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    // `token` is expected to be a subslice of the line, otherwise it's reported at the line end
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        let column = offset.min(self.text.len()) + 1;
+        return ParseError::new(self.day, self.number, column, token.as_bytes(), expected);
+    }
+    pub fn split_once(&self, text: &'a str, delimiter: char) -> Result<(&'a str, &'a str), ParseError> {
+        return text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(text, &format!("{delimiter:?}")));
+    }
+    pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        return text
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, &format!("{prefix:?}")));
+    }
+    pub fn strip_suffix(&self, text: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+        return text
+            .strip_suffix(suffix)
+            .ok_or_else(|| self.error(text, &format!("{suffix:?}")));
+    }
+    pub fn token(&self, token: Option<&'a str>, expected: &str) -> Result<&'a str, ParseError> {
+        return token.ok_or_else(|| self.error(&self.text[self.text.len()..], expected));
+    }
+    pub fn number<N: std::str::FromStr>(&self, token: &str) -> Result<N, ParseError> {
+        return token.parse::<N>().map_err(|_| self.error(token, "a number"));
+    }
+}
+
+#[derive(Clone)]
+pub struct InputLines<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub lines: std::str::Lines<'a>,
+}
+
+impl<'a> InputLines<'a> {
+    pub fn new(day: u8, input: &'a str) -> InputLines<'a> {
+        InputLines {
+            day,
+            number: 0,
+            lines: input.lines(),
+        }
+    }
+    pub fn expect(&mut self, expected: &str) -> Result<Line<'a>, ParseError> {
+        let number = self.number + 1;
+        return self
+            .next()
+            .ok_or_else(|| ParseError::new(self.day, number, 1, b"", expected));
+    }
+}
+
+impl<'a> Iterator for InputLines<'a> {
+    type Item = Line<'a>;
+    fn next(&mut self) -> Option<Line<'a>> {
+        let text = self.lines.next()?;
+        self.number += 1;
+        return Some(Line {
+            day: self.day,
+            number: self.number,
+            text,
+        });
+    }
+}
+
+pub trait ByteExtensions {
+    fn digit(&self) -> Option<i32>;
+    fn dot(&self) -> bool;
+}
+impl ByteExtensions for u8 {
+    fn digit(&self) -> Option<i32> {
+        if '0' as u8 <= *self && *self <= '9' as u8 {
+            Some((*self - '0' as u8) as i32)
+        } else {
+            None::<i32>
+        }
+    }
+    fn dot(&self) -> bool {
+        *self == '.' as u8
+    }
+}
+pub trait ByteSeqExtensions {
+    fn number_span(&self, mid: usize) -> Option<i32>;
+}
+impl ByteSeqExtensions for &[u8] {
+    fn number_span(&self, mid: usize) -> Option<i32> {
+        self[mid].digit()?;
+
+        let mut current = mid;
+        while current > 0 && self[current - 1].digit().is_some() {
+            current -= 1;
+        }
+        let mut number = 0;
+        while current < self.len() {
+            match self[current].digit() {
+                Some(digit) => {
+                    number = 10 * number + digit;
+                }
+                None => return Some(number),
+            }
+            current += 1;
+        }
+        return Some(number);
+    }
+}
+
+pub fn split_exact<const N: usize>(input: &str, delimiter: char) -> Result<[&str; N], &str> {
+    let mut elements = [""; N];
+    let mut index = 0;
+    for element in input.split(delimiter) {
+        if index >= N {
+            return Err("too many elements for split");
+        }
+        elements[index] = element;
+        index += 1;
+    }
+    if index < N {
+        return Err("too few elements for split");
+    }
+    return Ok(elements);
+}
@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant};
+
+use crate::solution::{Answer, Options, Part, Solution, SolveError};
+use crate::solvers::{
+    day01::Day1, day02::Day2, day03::Day3, day04::Day4, day05::Day5, day06::Day6, day07::Day7,
+    day07::Day7Str, day08::Day8, day08::Day8BruteForce, day09::Day9, day10::Day10,
+    day11::Day11, day12::Day12, day12::Day12Sampled, day17::Day17, day18::Day18,
+};
+
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Timing {
+    // None for solvers parsing while solving
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        return self.parse.unwrap_or_default() + self.solve;
+    }
+}
+
+pub type Solved = Result<(Answer, Timing), SolveError>;
+
+pub struct Solver {
+    pub day: u8,
+    pub variant: &'static str,
+    pub parts: &'static [Part],
+    pub randomized: bool,
+    pub solve: fn(&str, Part, &Options) -> Solved,
+}
+
+fn timed<S: Solution>(input: &str, part: Part, options: &Options) -> Solved {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parsed_at = Instant::now();
+    let answer = S::solve_with(parsed, part, options)?;
+    let timing = match S::PARSES {
+        true => Timing {
+            parse: Some(parsed_at - start),
+            solve: parsed_at.elapsed(),
+        },
+        false => Timing {
+            parse: None,
+            solve: start.elapsed(),
+        },
+    };
+    return Ok((answer, timing));
+}
+
+pub const fn solver<S: Solution>(day: u8, variant: &'static str) -> Solver {
+    Solver {
+        day,
+        variant,
+        parts: S::PARTS,
+        randomized: S::RANDOMIZED,
+        solve: timed::<S>,
+    }
+}
+
+// first registered variant answering a (day, part) pair is the default one
+#[rustfmt::skip]
+pub const SOLVERS: &[Solver] = &[
+    solver::<Day1>(1, "day1"),
+    solver::<Day2>(2, "day2"),
+    solver::<Day3>(3, "day3"),
+    solver::<Day4>(4, "day4"),
+    solver::<Day5>(5, "day5"),
+    solver::<Day6>(6, "day6"),
+    solver::<Day7>(7, "day7"),
+    solver::<Day7Str>(7, "day7_str"),
+    solver::<Day8>(8, "day8"),
+    solver::<Day8BruteForce>(8, "day8_hard_brute_force"),
+    solver::<Day9>(9, "day9"),
+    solver::<Day10>(10, "day10"),
+    solver::<Day11>(11, "day11"),
+    solver::<Day12>(12, "day12"),
+    solver::<Day12Sampled>(12, "day12_sampled"),
+    solver::<Day17>(17, "day17"),
+    solver::<Day18>(18, "day18"),
+];
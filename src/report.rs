@@ -0,0 +1,253 @@
+use std::io::Write;
+
+use crate::input::Check;
+use crate::registry::Timing;
+use crate::solution::{Answer, Part};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Format {
+    Json,
+    Tsv,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "json" => Some(Format::Json),
+            "tsv" => Some(Format::Tsv),
+            _ => None,
+        }
+    }
+}
+
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub variant: &'static str,
+    // None when the input doesn't have a known name (--input)
+    pub input: Option<String>,
+    // only for randomized solvers, written as a string like answers to survive JSON parsers
+    pub seed: Option<u64>,
+    pub outcome: Result<(Answer, Check), String>,
+    // medians in benchmark mode, not reported for errors
+    pub timing: Timing,
+}
+
+const COLUMNS: &[&str] = &[
+    "day", "part", "variant", "input", "seed", "answer", "exact", "std_error", "ci_low", "ci_high",
+    "samples", "parse_ns", "solve_ns", "elapsed_ns", "status", "expected", "error",
+];
+
+impl Record {
+    pub fn status(&self) -> &'static str {
+        return match &self.outcome {
+            Err(_) => "error",
+            Ok((_, Check::Unknown)) => "unknown",
+            Ok((_, Check::Pass)) => "ok",
+            Ok((_, Check::Fail(_))) => "fail",
+            Ok((_, Check::Imprecise(_))) => "imprecise",
+        };
+    }
+    // values in the order of COLUMNS
+    fn values(&self) -> Vec<Value> {
+        let answer = self.outcome.as_ref().ok().map(|(answer, _)| *answer);
+        let plain = |answer: Answer| answer.to_string().trim_start_matches('~').to_string();
+        let estimate = match answer {
+            Some(Answer::Approx(estimate)) => Some(estimate),
+            _ => None,
+        };
+        let interval = estimate.map(|estimate| estimate.interval());
+        let expected = match &self.outcome {
+            Ok((_, Check::Fail(expected) | Check::Imprecise(expected))) => Some(expected.clone()),
+            Ok((answer, Check::Pass)) => Some(plain(*answer)),
+            _ => None,
+        };
+        let timing = answer.map(|_| self.timing);
+        return vec![
+            number(Some(self.day)),
+            text(Some(self.part.name())),
+            text(Some(self.variant)),
+            text(self.input.as_ref()),
+            text(self.seed),
+            text(answer.map(plain)),
+            number(answer.map(|answer| answer.is_exact())),
+            number(estimate.map(|estimate| estimate.std_error)),
+            number(interval.map(|(low, _)| low)),
+            number(interval.map(|(_, high)| high)),
+            number(estimate.map(|estimate| estimate.samples)),
+            number(timing.and_then(|timing| timing.parse).map(|parse| parse.as_nanos())),
+            number(timing.map(|timing| timing.solve.as_nanos())),
+            number(timing.map(|timing| timing.total().as_nanos())),
+            text(Some(self.status())),
+            text(expected),
+            text(self.outcome.as_ref().err()),
+        ];
+    }
+}
+
+enum Value {
+    Missing,
+    Number(String),
+    // quoted in JSON
+    Text(String),
+}
+
+fn number<T: ToString>(value: Option<T>) -> Value {
+    return value.map_or(Value::Missing, |value| Value::Number(value.to_string()));
+}
+
+fn text<T: ToString>(value: Option<T>) -> Value {
+    return value.map_or(Value::Missing, |value| Value::Text(value.to_string()));
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+// tabs and newlines can only come from error messages, they would break the row
+fn tsv_field(value: &str) -> String {
+    return value.replace(['\t', '\n', '\r'], " ");
+}
+
+pub fn write_report(
+    format: Format,
+    records: &[Record],
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    match format {
+        Format::Json => {
+            writeln!(out, "[")?;
+            for (i, record) in records.iter().enumerate() {
+                let fields: Vec<String> = COLUMNS
+                    .iter()
+                    .zip(record.values())
+                    .map(|(column, value)| {
+                        let value = match value {
+                            Value::Missing => "null".to_string(),
+                            Value::Number(value) => value,
+                            Value::Text(value) => json_string(&value),
+                        };
+                        format!("\"{column}\": {value}")
+                    })
+                    .collect();
+                let separator = if i + 1 < records.len() { "," } else { "" };
+                writeln!(out, "  {{{}}}{separator}", fields.join(", "))?;
+            }
+            writeln!(out, "]")?;
+        }
+        Format::Tsv => {
+            writeln!(out, "{}", COLUMNS.join("\t"))?;
+            for record in records {
+                let fields: Vec<String> = record
+                    .values()
+                    .into_iter()
+                    .map(|value| match value {
+                        Value::Missing => String::new(),
+                        Value::Number(value) | Value::Text(value) => tsv_field(&value),
+                    })
+                    .collect();
+                writeln!(out, "{}", fields.join("\t"))?;
+            }
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Estimate;
+    use std::time::Duration;
+
+    #[test]
+    fn reports_keep_large_answers_and_errors_intact() {
+        let timing = Timing {
+            parse: Some(Duration::from_nanos(5)),
+            solve: Duration::from_nanos(10),
+        };
+        let records = [
+            Record {
+                day: 8,
+                part: Part::Hard,
+                variant: "day8",
+                input: Some("input08".to_string()),
+                seed: None,
+                outcome: Ok((Answer::I128(11283670395017), Check::Pass)),
+                timing,
+            },
+            Record {
+                day: 2,
+                part: Part::Easy,
+                variant: "day2",
+                input: None,
+                seed: Some(7),
+                outcome: Err("expected a color, found \"blux\"".to_string()),
+                timing,
+            },
+            Record {
+                day: 12,
+                part: Part::Easy,
+                variant: "day12_sampled",
+                input: Some("example12".to_string()),
+                seed: Some(7),
+                outcome: Ok((
+                    Answer::Approx(Estimate {
+                        value: 21,
+                        std_error: 0.5,
+                        samples: 4096,
+                    }),
+                    Check::Pass,
+                )),
+                timing,
+            },
+        ];
+
+        let mut json = Vec::new();
+        write_report(Format::Json, &records, &mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.contains(
+            "\"answer\": \"11283670395017\", \"exact\": true, \"std_error\": null, \
+             \"ci_low\": null, \"ci_high\": null, \"samples\": null, \"parse_ns\": 5, \
+             \"solve_ns\": 10, \"elapsed_ns\": 15, \"status\": \"ok\", \
+             \"expected\": \"11283670395017\""
+        ));
+        assert!(json.contains("\"input\": null, \"seed\": \"7\", \"answer\": null"));
+        assert!(json.contains("\"elapsed_ns\": null, \"status\": \"error\""));
+        assert!(json.contains("\"error\": \"expected a color, found \\\"blux\\\"\"},\n"));
+
+        let mut tsv = Vec::new();
+        write_report(Format::Tsv, &records, &mut tsv).unwrap();
+        let tsv = String::from_utf8(tsv).unwrap();
+        let rows: Vec<&str> = tsv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        let fields = |row: &str| row.split('\t').map(|x| x.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            fields(rows[1]),
+            [
+                "8", "hard", "day8", "input08", "", "11283670395017", "true", "", "", "", "", "5",
+                "10", "15", "ok", "11283670395017", ""
+            ]
+        );
+        assert_eq!(fields(rows[2]).len(), COLUMNS.len());
+        assert_eq!(
+            fields(rows[3]),
+            [
+                "12", "easy", "day12_sampled", "example12", "7", "21", "false", "0.5", "20.02",
+                "21.98", "4096", "5", "10", "15", "ok", "21", ""
+            ]
+        );
+    }
+}
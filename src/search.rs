@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+
+// breadth-first steps from the nearest of `starts` through the cells `passable` accepts, moving
+// by `offsets`, None for the cells out of reach, starts that aren't passable are ignored
+pub fn distances<T>(
+    grid: &Grid<T>,
+    starts: &[Point],
+    offsets: &[Point],
+    passable: impl Fn(&T) -> bool,
+) -> Grid<Option<i32>> {
+    let mut distances = Grid::new(grid.width, grid.height, None);
+    let mut queue = VecDeque::new();
+    for &start in starts {
+        if grid.get(start).is_some_and(&passable) && distances.at(start).is_none() {
+            distances.set(start, Some(0));
+            queue.push_back(start);
+        }
+    }
+    while let Some(position) = queue.pop_front() {
+        let distance = distances.at(position).unwrap();
+        for (neighbour, value) in grid.neighbours(position, offsets) {
+            if passable(value) && distances.at(neighbour).is_none() {
+                distances.set(neighbour, Some(distance + 1));
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    return distances;
+}
+
+// flood fill, the cells reachable from `starts`
+pub fn reachable<T>(
+    grid: &Grid<T>,
+    starts: &[Point],
+    offsets: &[Point],
+    passable: impl Fn(&T) -> bool,
+) -> Grid<bool> {
+    let distances = distances(grid, starts, offsets, passable);
+    return Grid {
+        cells: distances.cells.iter().map(|distance| distance.is_some()).collect(),
+        width: grid.width,
+        height: grid.height,
+        outside: false,
+    };
+}
+
+// labels the passable cells with the number of their connected component, in the order of
+// their first cell, and returns the number of components
+pub fn components<T>(
+    grid: &Grid<T>,
+    offsets: &[Point],
+    passable: impl Fn(&T) -> bool,
+) -> (Grid<Option<usize>>, usize) {
+    let mut labels = Grid::new(grid.width, grid.height, None);
+    let mut count = 0;
+    let mut queue = VecDeque::new();
+    for start in grid.points() {
+        if labels.at(start).is_some() || !passable(grid.get(start).unwrap()) {
+            continue;
+        }
+        labels.set(start, Some(count));
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            for (neighbour, value) in grid.neighbours(position, offsets) {
+                if passable(value) && labels.at(neighbour).is_none() {
+                    labels.set(neighbour, Some(count));
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        count += 1;
+    }
+    return (labels, count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{DIRECTIONS_4, DIRECTIONS_8};
+
+    #[test]
+    fn searches_follow_passable_cells() {
+        let grid = Grid::parse("..#.\n.##.\n...#\n##.#").unwrap();
+        let open = |&x: &u8| x == b'.';
+        let corner = Point { x: 0, y: 0 };
+
+        let steps = distances(&grid, &[corner], DIRECTIONS_4, open);
+        assert_eq!(steps.at(Point { x: 3, y: 2 }), Some(5));
+        assert_eq!(steps.at(Point { x: 0, y: 3 }), None);
+        assert_eq!(steps.at(Point { x: 0, y: 2 }), None);
+        let both = [corner, Point { x: 3, y: 2 }];
+        assert_eq!(distances(&grid, &both, DIRECTIONS_4, open).at(Point { x: 2, y: 1 }), Some(2));
+
+        let filled = reachable(&grid, &[corner], DIRECTIONS_4, open);
+        assert_eq!(filled.cells.iter().filter(|&&x| x).count(), 7);
+        assert!(!reachable(&grid, &[Point { x: 0, y: 2 }], DIRECTIONS_4, open).cells[0]);
+
+        let (labels, count) = components(&grid, DIRECTIONS_4, open);
+        assert_eq!(count, 2);
+        assert_eq!(labels.at(Point { x: 3, y: 2 }), Some(0));
+        assert_eq!(labels.at(Point { x: 1, y: 3 }), Some(1));
+        assert_eq!(labels.at(Point { x: 0, y: 2 }), None);
+        assert_eq!(components(&grid, DIRECTIONS_8, |&x| x == b'#').1, 2);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::parsing::ParseError;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Part {
+    Easy,
+    Hard,
+}
+
+impl Part {
+    pub fn parse(name: &str) -> Option<Part> {
+        match name {
+            "easy" | "1" => Some(Part::Easy),
+            "hard" | "2" => Some(Part::Hard),
+            _ => None,
+        }
+    }
+    pub fn pick<E: Into<Answer>, H: Into<Answer>>(&self, easy: E, hard: H) -> Answer {
+        match self {
+            Part::Easy => easy.into(),
+            Part::Hard => hard.into(),
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            Part::Easy => "easy",
+            Part::Hard => "hard",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Answer {
+    I64(i64),
+    I128(i128),
+    U128(u128),
+    // estimated by sampling, not guaranteed to be exact
+    Approx(Estimate),
+}
+
+impl Answer {
+    pub fn is_exact(&self) -> bool {
+        return !matches!(self, Answer::Approx(_));
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Estimate {
+    pub value: i128,
+    pub std_error: f64,
+    pub samples: u64,
+}
+
+impl Estimate {
+    // normal approximation, 95% of the intervals cover the exact answer
+    pub fn interval(&self) -> (f64, f64) {
+        let margin = 1.96 * self.std_error;
+        return (self.value as f64 - margin, self.value as f64 + margin);
+    }
+}
+
+impl std::fmt::Display for Estimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (low, high) = self.interval();
+        let (std_error, samples) = (self.std_error, self.samples);
+        write!(f, "std error {std_error:.2}, 95% CI [{low:.1}, {high:.1}], {samples} samples")
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::I64(value as i64)
+    }
+}
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::I64(value)
+    }
+}
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::I64(value as i64)
+    }
+}
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        Answer::I128(value)
+    }
+}
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::U128(value)
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::I64(value) => write!(f, "{value}"),
+            Answer::I128(value) => write!(f, "{value}"),
+            Answer::U128(value) => write!(f, "{value}"),
+            Answer::Approx(estimate) => write!(f, "~{}", estimate.value),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum SolveError {
+    Parse(ParseError),
+    // the input is well formed but has no answer, with the reason why
+    NoSolution(String),
+}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        SolveError::Parse(error)
+    }
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse(error) => write!(f, "{error}"),
+            SolveError::NoSolution(reason) => write!(f, "no solution, {reason}"),
+        }
+    }
+}
+
+// runner settings for the solvers which need them
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Options {
+    // seeds the random number generators of randomized solvers
+    pub seed: u64,
+    // worker threads for solvers able to use them, 0 counts as one
+    pub threads: usize,
+}
+
+pub trait Solution {
+    // parts this solution answers, `solve` is only called with one of them
+    const PARTS: &'static [Part];
+    // answers depend on Options::seed
+    const RANDOMIZED: bool = false;
+    // false when `parse` only trims the text and the parsing happens while solving, the parse
+    // time is then counted as solve time
+    const PARSES: bool = true;
+    // input as the solver works with it, solvers parsing line by line while solving keep the text
+    type Parsed<'a>;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+    fn solve(parsed: Self::Parsed<'_>, part: Part) -> Result<Answer, SolveError>;
+    fn solve_with(
+        parsed: Self::Parsed<'_>,
+        part: Part,
+        _options: &Options,
+    ) -> Result<Answer, SolveError> {
+        return Self::solve(parsed, part);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::parsing::{InputLines, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+pub const DAY1_EASY_PATTERN: &[&[&str]; 10] = &[
+    &["0"],
+    &["1"],
+    &["2"],
+    &["3"],
+    &["4"],
+    &["5"],
+    &["6"],
+    &["7"],
+    &["8"],
+    &["9"],
+];
+pub const DAY1_HARD_PATTERN: &[&[&str]; 10] = &[
+    &["0"],
+    &["1", "one"],
+    &["2", "two"],
+    &["3", "three"],
+    &["4", "four"],
+    &["5", "five"],
+    &["6", "six"],
+    &["7", "seven"],
+    &["8", "eight"],
+    &["9", "nine"],
+];
+
+pub fn day1(input: &str, patterns: &[&[&str]; 10]) -> Result<i32, ParseError> {
+    let mut sum = 0;
+    for line in InputLines::new(1, input) {
+        let (mut first_digit, mut last_digit) = (None::<i32>, None::<i32>);
+        for i in 0..line.text.len() {
+            'digit: for digit in 0..10 {
+                for pattern in patterns[digit] {
+                    if line.text[i..].starts_with(pattern) {
+                        first_digit = if first_digit.is_none() {
+                            Some(digit as i32)
+                        } else {
+                            first_digit
+                        };
+                        last_digit = Some(digit as i32);
+                        break 'digit;
+                    }
+                }
+            }
+        }
+        let (Some(first_digit), Some(last_digit)) = (first_digit, last_digit) else {
+            return Err(line.error(line.text, "a digit"));
+        };
+        let value = first_digit * 10 + last_digit;
+        sum += value;
+    }
+    return Ok(sum);
+}
+
+pub struct Day1;
+impl Solution for Day1 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        let patterns = match part {
+            Part::Easy => DAY1_EASY_PATTERN,
+            Part::Hard => DAY1_HARD_PATTERN,
+        };
+        return Ok(day1(input, patterns)?.into());
+    }
+}
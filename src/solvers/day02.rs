@@ -0,0 +1,87 @@
+use crate::parsing::{InputLines, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+pub struct Bag {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
+}
+
+pub const DAY2_BAG: Bag = Bag {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+pub fn day2(input: &str, bag: Bag) -> Result<(i32, i32), ParseError> {
+    let mut count_valid = 0;
+    let mut power_sum = 0;
+    for line in InputLines::new(2, input) {
+        let game = line.strip_prefix(line.text, "Game ")?;
+        let (game_id, rounds) = line.split_once(game, ':')?;
+        let mut valid = true;
+        let mut bag_prediction = Bag {
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+
+        for round in rounds.split(';') {
+            let mut round_bag = Bag {
+                red: 0,
+                green: 0,
+                blue: 0,
+            };
+            for cube in round.trim().split(',') {
+                let (count, color) = line.split_once(cube.trim(), ' ')?;
+                let field = match color.trim() {
+                    "red" => &mut round_bag.red,
+                    "green" => &mut round_bag.green,
+                    "blue" => &mut round_bag.blue,
+                    _ => return Err(line.error(color, "a color")),
+                };
+                *field += line.number::<i32>(count)?;
+            }
+            if bag.red < round_bag.red || bag.green < round_bag.green || bag.blue < round_bag.blue {
+                valid = false;
+            }
+            bag_prediction.red = bag_prediction.red.max(round_bag.red);
+            bag_prediction.green = bag_prediction.green.max(round_bag.green);
+            bag_prediction.blue = bag_prediction.blue.max(round_bag.blue);
+        }
+        power_sum += bag_prediction.red * bag_prediction.green * bag_prediction.blue;
+        if valid {
+            count_valid += line.number::<i32>(game_id)?;
+        }
+    }
+    return Ok((count_valid, power_sum));
+}
+
+pub struct Day2;
+impl Solution for Day2 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        let (count_valid, power_sum) = day2(input, DAY2_BAG)?;
+        return Ok(part.pick(count_valid, power_sum));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let error = day2("Game 1: 3 blue\nGame 2: 4 blux", DAY2_BAG).unwrap_err();
+        assert_eq!((error.line, error.column, error.token.as_str()), (2, 11, "blux"));
+        assert_eq!(
+            error.to_string(),
+            "day02 line 2 column 11: expected a color, found \"blux\""
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::geometry::{Point, DIRECTIONS_6};
+use crate::grid::Grid;
+use crate::parsing::{ByteExtensions, ByteSeqExtensions, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+pub fn day3_easy(grid: &Grid<u8>) -> i32 {
+    let mut sum = 0;
+    for row in 0..grid.height {
+        let mut col = 0;
+        while col < grid.width {
+            if grid.at(Point { x: row, y: col }).digit().is_none() {
+                col += 1;
+                continue;
+            }
+            // anything but '.' around the number marks it, digits above or below included
+            let (mut number, mut is_part_number) =
+                (0, !grid.at(Point { x: row, y: col - 1 }).dot());
+            while let Some(digit) = grid.at(Point { x: row, y: col }).digit() {
+                number = 10 * number + digit;
+                let position = Point { x: row, y: col };
+                is_part_number |= grid.neighbours(position, DIRECTIONS_6).any(|(_, &x)| !x.dot());
+                col += 1;
+            }
+            is_part_number |= !grid.at(Point { x: row, y: col }).dot();
+            if is_part_number {
+                sum += number;
+            }
+        }
+    }
+    return sum;
+}
+
+struct Gear {
+    drivers_count: usize,
+    drivers: [i32; 2],
+}
+
+impl Gear {
+    pub fn add_driver(&mut self, driver: Option<i32>) {
+        if self.drivers_count >= 2 || driver.is_none() {
+            return;
+        }
+        self.drivers[self.drivers_count] = driver.unwrap();
+        self.drivers_count += 1;
+    }
+    pub fn power(&self) -> Option<i32> {
+        if self.drivers_count == 2 {
+            Some(self.drivers[0] * self.drivers[1])
+        } else {
+            None::<i32>
+        }
+    }
+}
+
+pub fn day3_hard(grid: &Grid<u8>) -> i32 {
+    let number_at = |position: Point| {
+        grid.get(position)?;
+        return grid.row(position.x).number_span(position.y as usize);
+    };
+    let mut sum = 0;
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            if grid.at(Point { x: row, y: col }) != '*' as u8 {
+                continue;
+            }
+            let mut gear = Gear {
+                drivers_count: 0,
+                drivers: [0; 2],
+            };
+            gear.add_driver(number_at(Point { x: row, y: col - 1 }));
+            gear.add_driver(number_at(Point { x: row, y: col + 1 }));
+            for drow in [-1, 1] {
+                let driver = number_at(Point {
+                    x: row + drow,
+                    y: col,
+                });
+                gear.add_driver(driver);
+                if driver.is_some() {
+                    continue;
+                }
+                gear.add_driver(number_at(Point {
+                    x: row + drow,
+                    y: col - 1,
+                }));
+                gear.add_driver(number_at(Point {
+                    x: row + drow,
+                    y: col + 1,
+                }));
+            }
+            sum += gear.power().unwrap_or(0);
+        }
+    }
+    return sum;
+}
+
+pub struct Day3;
+impl Solution for Day3 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Grid<u8>;
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        return Grid::parse(input).map_err(|error| error.into_parse_error(3));
+    }
+    fn solve(grid: Grid<u8>, part: Part) -> Result<Answer, SolveError> {
+        return match part {
+            Part::Easy => Ok(day3_easy(&grid).into()),
+            Part::Hard => Ok(day3_hard(&grid).into()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_above_or_below_mark_part_numbers() {
+        assert_eq!(day3_easy(&Grid::parse("12.\n.3.").unwrap()), 15);
+        assert_eq!(day3_easy(&Grid::parse("12.\n...\n.3.").unwrap()), 0);
+        assert_eq!(day3_easy(&Grid::parse("12*3\n....").unwrap()), 15);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::parsing::{InputLines, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+pub fn day4(input: &str) -> Result<(i32, u128), ParseError> {
+    let mut sum = 0;
+    let mut scratchcards = 0;
+    let mut copies_count = [1 as u128; 32];
+    for (i, line) in InputLines::new(4, input).enumerate() {
+        let (_, cards) = line.split_once(line.text, ':')?;
+        let (winning, hand) = line.split_once(cards, '|')?;
+        let mut winning_mask = 0 as u128;
+        for winning_card in winning.split(' ') {
+            let winning_card = winning_card.trim();
+            if winning_card.len() == 0 {
+                continue;
+            }
+            let number = line.number::<u8>(winning_card)?;
+            if number >= 128 {
+                return Err(line.error(winning_card, "a card below 128"));
+            }
+            winning_mask |= (1 as u128) << number;
+        }
+        let mut wins_count = 0;
+        for hand_card in hand.split(' ') {
+            let hand_card = hand_card.trim();
+            if hand_card.len() == 0 {
+                continue;
+            }
+            let number = line.number::<u8>(hand_card)?;
+            if number >= 128 {
+                return Err(line.error(hand_card, "a card below 128"));
+            }
+            if winning_mask & ((1 as u128) << number) > 0 {
+                wins_count += 1;
+            }
+        }
+        if wins_count >= copies_count.len() {
+            return Err(line.error(hand, "less than 32 winning cards"));
+        }
+        scratchcards += copies_count[i % copies_count.len()];
+        for next in 1..wins_count + 1 {
+            copies_count[(i + next) % copies_count.len()] += copies_count[i % copies_count.len()];
+        }
+        copies_count[i % copies_count.len()] = 1;
+
+        sum += if wins_count > 0 {
+            1 << (wins_count - 1)
+        } else {
+            0
+        };
+    }
+    return Ok((sum, scratchcards));
+}
+
+pub struct Day4;
+impl Solution for Day4 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        let (sum, scratchcards) = day4(input)?;
+        return Ok(part.pick(sum, scratchcards));
+    }
+}
@@ -0,0 +1,234 @@
+use crate::parsing::{split_exact, InputLines, Line, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+#[derive(Copy, Clone, Debug)]
+pub struct MapRange {
+    pub destination: i64,
+    pub source: i64,
+    pub length: i64,
+}
+
+impl MapRange {
+    fn contains_source(&self, value: i64) -> bool {
+        return self.source <= value && value < self.source + self.length;
+    }
+    fn contains_destination(&self, value: i64) -> bool {
+        return self.destination <= value && value < self.destination + self.length;
+    }
+}
+
+// one stage of the chain, like seed-to-soil
+pub struct Map<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    // sorted by source, source ranges never overlap
+    pub ranges: Vec<MapRange>,
+}
+
+impl<'a> Map<'a> {
+    fn range_of(&self, value: i64) -> Option<&MapRange> {
+        let index = self.ranges.partition_point(|range| range.source + range.length <= value);
+        return self.ranges.get(index).filter(|range| range.contains_source(value));
+    }
+    pub fn forward(&self, value: i64) -> i64 {
+        return match self.range_of(value) {
+            Some(range) => range.destination + value - range.source,
+            None => value,
+        };
+    }
+    // destination ranges can overlap, so a value can come from several sources
+    pub fn inverse(&self, value: i64) -> Vec<i64> {
+        let mut sources: Vec<i64> = self
+            .ranges
+            .iter()
+            .filter(|range| range.contains_destination(value))
+            .map(|range| range.source + value - range.destination)
+            .collect();
+        // unmapped values are kept as they are
+        if self.range_of(value).is_none() {
+            sources.push(value);
+        }
+        sources.sort();
+        return sources;
+    }
+    // maps half-open intervals, splitting them where they cross the ends of the ranges
+    pub fn map_intervals(&self, intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+        let mut mapped = Vec::with_capacity(intervals.len());
+        for &(mut start, end) in intervals {
+            for range in &self.ranges {
+                if start >= end || range.source >= end {
+                    break;
+                }
+                if range.source + range.length <= start {
+                    continue;
+                }
+                if start < range.source {
+                    mapped.push((start, range.source));
+                    start = range.source;
+                }
+                let stop = end.min(range.source + range.length);
+                let offset = range.destination - range.source;
+                mapped.push((start + offset, stop + offset));
+                start = stop;
+            }
+            if start < end {
+                mapped.push((start, end));
+            }
+        }
+        return mapped;
+    }
+}
+
+pub struct Almanac<'a> {
+    pub seeds_line: Line<'a>,
+    pub seeds: Vec<i64>,
+    // each map goes from the category the previous one goes to
+    pub maps: Vec<Map<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn stage(&self, from: &str) -> Option<&Map<'a>> {
+        return self.maps.iter().find(|map| map.from == from);
+    }
+    pub fn location(&self, seed: i64) -> i64 {
+        return self.maps.iter().fold(seed, |value, map| map.forward(value));
+    }
+    // every seed, listed or not, that ends up at `location`
+    pub fn seeds_at(&self, location: i64) -> Vec<i64> {
+        let mut values = vec![location];
+        for map in self.maps.iter().rev() {
+            values = values.iter().flat_map(|&value| map.inverse(value)).collect();
+            values.sort();
+            values.dedup();
+        }
+        return values;
+    }
+}
+
+pub fn parse_almanac(input: &str) -> Result<Almanac<'_>, ParseError> {
+    let mut lines = InputLines::new(5, input);
+    let seeds_line = lines.expect("seeds")?;
+    let (_, seeds) = seeds_line.split_once(seeds_line.text, ':')?;
+    let mut almanac = Almanac {
+        seeds_line,
+        seeds: Vec::new(),
+        maps: Vec::new(),
+    };
+    for seed in seeds.trim().split(' ') {
+        almanac.seeds.push(seeds_line.number::<i64>(seed.trim())?);
+    }
+    for line in lines {
+        if line.text.len() == 0 {
+            continue;
+        }
+        if let Some(header) = line.text.strip_suffix(':') {
+            let name = line.strip_suffix(header, " map")?;
+            let (from, to) = name
+                .split_once("-to-")
+                .ok_or_else(|| line.error(name, "a map name like seed-to-soil"))?;
+            let previous = almanac.maps.last().map_or("seed", |map| map.to);
+            if from != previous {
+                return Err(line.error(from, &format!("{previous:?}")));
+            }
+            almanac.maps.push(Map {
+                from,
+                to,
+                ranges: Vec::new(),
+            });
+            continue;
+        }
+        let [dst_range_start, src_range_start, range_len] =
+            split_exact::<3>(line.text.trim(), ' ')
+                .map_err(|_| line.error(line.text, "three numbers"))?;
+        let range = MapRange {
+            destination: line.number::<i64>(dst_range_start)?,
+            source: line.number::<i64>(src_range_start)?,
+            length: line.number::<i64>(range_len)?,
+        };
+        if range.length < 0 {
+            return Err(line.error(range_len, "a non-negative length"));
+        }
+        let map = match almanac.maps.last_mut() {
+            Some(map) => map,
+            None => return Err(line.error(line.text, "a map header")),
+        };
+        let overlaps = map.ranges.iter().any(|other| {
+            other.source < range.source + range.length && range.source < other.source + other.length
+        });
+        if overlaps {
+            return Err(line.error(src_range_start, "a source range disjoint from the others"));
+        }
+        map.ranges.push(range);
+    }
+    for map in &mut almanac.maps {
+        map.ranges.sort_by_key(|range| range.source);
+    }
+    return Ok(almanac);
+}
+
+pub fn day5(almanac: &Almanac, part: Part) -> Result<i64, ParseError> {
+    let seeds_line = almanac.seeds_line;
+    let min_location = match part {
+        Part::Easy => almanac.seeds.iter().map(|&seed| almanac.location(seed)).min(),
+        Part::Hard => {
+            if !almanac.seeds.len().is_multiple_of(2) {
+                let end = &seeds_line.text[seeds_line.text.len()..];
+                return Err(seeds_line.error(end, "a length for every seed range"));
+            }
+            let pairs = almanac.seeds.chunks(2);
+            let mut intervals: Vec<(i64, i64)> =
+                pairs.map(|pair| (pair[0], pair[0] + pair[1])).collect();
+            for map in &almanac.maps {
+                intervals = map.map_intervals(&intervals);
+            }
+            intervals.iter().map(|&(start, _)| start).min()
+        }
+    };
+    return min_location.ok_or_else(|| seeds_line.error(seeds_line.text, "seeds"));
+}
+
+pub struct Day5;
+impl Solution for Day5 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Almanac<'a>;
+    fn parse(input: &str) -> Result<Almanac<'_>, ParseError> {
+        return parse_almanac(input.trim());
+    }
+    fn solve(almanac: Almanac<'_>, part: Part) -> Result<Answer, SolveError> {
+        return Ok(day5(&almanac, part)?.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn almanac_lookups_go_both_ways() {
+        let almanac = parse_almanac(include_str!("../inputs/example05.txt").trim()).unwrap();
+        assert_eq!(almanac.stage("soil").unwrap().to, "fertilizer");
+        assert_eq!(almanac.stage("seed").unwrap().forward(79), 81);
+        assert_eq!(almanac.location(13), 35);
+        assert_eq!(almanac.seeds_at(35), [13]);
+        for seed in 0..100 {
+            assert!(almanac.seeds_at(almanac.location(seed)).contains(&seed));
+        }
+        // soil 51 comes from seed 99 through 50 98 2, seed 51 itself goes to soil 53
+        assert_eq!(almanac.stage("seed").unwrap().inverse(51), [99]);
+        assert_eq!(almanac.stage("seed").unwrap().inverse(98), [96]);
+        assert_eq!(almanac.stage("seed").unwrap().inverse(10), [10]);
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let error = parse_almanac("seeds: 79 14\n\nseed-to-soil map:\n50 98").err().unwrap();
+        assert_eq!((error.line, error.column, error.token.as_str()), (4, 1, "50 98"));
+        let almanac = "seeds: 79\n\nseed-to-soil map:\n50 98 2\n52 50 49";
+        let error = parse_almanac(almanac).err().unwrap();
+        assert_eq!((error.line, error.column, error.token.as_str()), (5, 4, "50"));
+        let almanac = "seeds: 79\n\nseed-to-soil map:\n50 98 2\n\nwater-to-light map:";
+        let error = parse_almanac(almanac).err().unwrap();
+        assert_eq!((error.line, error.column, error.token.as_str()), (6, 1, "water"));
+        assert_eq!(error.expected, "\"soil\"");
+    }
+}
@@ -0,0 +1,87 @@
+use crate::parsing::{ByteExtensions, InputLines, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+pub fn count_winning_options(time: i64, distance: i64) -> i64 {
+    // at best the boat ties the record
+    if time * time <= 4 * distance {
+        return 0;
+    }
+    let (time, distance) = (time as f64, distance as f64);
+    let min_duration = time / 2.0 - (time * time - 4.0 * distance).sqrt() / 2.0;
+    let max_duration = time / 2.0 + (time * time - 4.0 * distance).sqrt() / 2.0;
+    return (max_duration.ceil() - min_duration.floor() - 1.0) as i64;
+}
+
+pub fn day6(input: &str) -> Result<(i64, i64), ParseError> {
+    let mut lines = InputLines::new(6, input);
+    let times_line = lines.expect("times")?;
+    let distances_line = lines.expect("distances")?;
+    let times = times_line.split_once(times_line.text, ':')?.1;
+    let distances = distances_line.split_once(distances_line.text, ':')?.1;
+    let mut time_iterator = times.trim().split(' ');
+    let mut distance_iterator = distances.trim().split(' ');
+    let mut mult = 1;
+    let (mut mega_time, mut mega_distance) = (0 as i64, 0 as i64);
+    loop {
+        let mut time = time_iterator.next();
+        while time.is_some() && time.unwrap().len() == 0 {
+            time = time_iterator.next();
+        }
+        let mut distance = distance_iterator.next();
+        while distance.is_some() && distance.unwrap().len() == 0 {
+            distance = distance_iterator.next();
+        }
+        if time.is_none() && distance.is_none() {
+            break;
+        }
+        let time = times_line.token(time, "a time for every distance")?;
+        let distance = distances_line.token(distance, "a distance for every time")?;
+        for c in time.as_bytes() {
+            let digit = c.digit().ok_or_else(|| times_line.error(time, "a number"))?;
+            mega_time = mega_time * 10 + digit as i64;
+        }
+        for c in distance.as_bytes() {
+            let digit = c.digit().ok_or_else(|| distances_line.error(distance, "a number"))?;
+            mega_distance = mega_distance * 10 + digit as i64;
+        }
+        let time = times_line.number::<i64>(time)?;
+        let distance = distances_line.number::<i64>(distance)?;
+        mult *= count_winning_options(time, distance);
+    }
+    let mega_options = count_winning_options(mega_time, mega_distance);
+    return Ok((mult, mega_options));
+}
+
+pub struct Day6;
+impl Solution for Day6 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        let (mult, mega_options) = day6(input)?;
+        return Ok(part.pick(mult, mega_options));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let error = day6("Time: 7 15").unwrap_err();
+        assert_eq!((error.line, error.column, error.token.as_str()), (2, 1, ""));
+    }
+
+    #[test]
+    fn lost_races_have_no_winning_options() {
+        assert_eq!(count_winning_options(3, 9), 0);
+        assert_eq!(count_winning_options(4, 4), 0);
+        assert_eq!(count_winning_options(4, 3), 1);
+        assert_eq!(count_winning_options(30, 200), 9);
+        assert_eq!(day6("Time: 3 7\nDistance: 9 9"), Ok((0, 32)));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::parsing::{ByteExtensions, InputLines, Line, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+#[derive(PartialEq, PartialOrd, Debug)]
+pub enum CombinationType {
+    Five,
+    Four,
+    FullHouse,
+    Three,
+    TwoPair,
+    OnePair,
+    High,
+}
+pub struct Hand {
+    combination: CombinationType,
+    cards: [u8; 5],
+}
+
+pub const SIMPLE_CARDS: &[u8] = b"AKQJT98765432";
+pub const JOKER_CARDS: &[u8] = b"AKQT98765432J";
+pub trait GameRules {
+    fn card_order(card: u8) -> usize;
+    fn parse_hand(hand: &[u8]) -> Hand;
+}
+
+pub struct SimpleGame;
+impl GameRules for SimpleGame {
+    fn card_order(card: u8) -> usize {
+        for i in 0..SIMPLE_CARDS.len() {
+            if SIMPLE_CARDS[i] == card {
+                return i;
+            }
+        }
+        panic!("unexpected card");
+    }
+    fn parse_hand(hand: &[u8]) -> Hand {
+        let mut original_cards = [0 as u8; 5];
+        original_cards.copy_from_slice(hand);
+
+        let mut sorted_cards = original_cards;
+        sorted_cards.sort();
+        let mut runs = 0;
+        let mut lone_cards = 0;
+        for i in 0..sorted_cards.len() {
+            runs += if i == 0 || sorted_cards[i - 1] != sorted_cards[i] {
+                1
+            } else {
+                0
+            };
+            lone_cards += if i != 0 && sorted_cards[i - 1] == sorted_cards[i]
+                || i + 1 < sorted_cards.len() && sorted_cards[i + 1] == sorted_cards[i]
+            {
+                0
+            } else {
+                1
+            };
+        }
+
+        return if sorted_cards[0] == sorted_cards[4] {
+            Hand {
+                combination: CombinationType::Five,
+                cards: original_cards,
+            }
+        } else if sorted_cards[0] == sorted_cards[3] || sorted_cards[1] == sorted_cards[4] {
+            Hand {
+                combination: CombinationType::Four,
+                cards: original_cards,
+            }
+        } else if sorted_cards[0] == sorted_cards[2] && sorted_cards[3] == sorted_cards[4]
+            || sorted_cards[0] == sorted_cards[1] && sorted_cards[2] == sorted_cards[4]
+        {
+            Hand {
+                combination: CombinationType::FullHouse,
+                cards: original_cards,
+            }
+        } else if runs == 3 && lone_cards == 2 {
+            Hand {
+                combination: CombinationType::Three,
+                cards: original_cards,
+            }
+        } else if runs == 3 {
+            Hand {
+                combination: CombinationType::TwoPair,
+                cards: original_cards,
+            }
+        } else if runs == 4 {
+            Hand {
+                combination: CombinationType::OnePair,
+                cards: original_cards,
+            }
+        } else {
+            Hand {
+                combination: CombinationType::High,
+                cards: original_cards,
+            }
+        };
+    }
+}
+
+pub struct JokerGame;
+impl GameRules for JokerGame {
+    fn card_order(card: u8) -> usize {
+        for i in 0..JOKER_CARDS.len() {
+            if JOKER_CARDS[i] == card {
+                return i;
+            }
+        }
+        panic!("unexpected card");
+    }
+    fn parse_hand(hand: &[u8]) -> Hand {
+        if !hand.contains(&b'J') {
+            return SimpleGame::parse_hand(hand);
+        }
+
+        let mut original_cards = [0 as u8; 5];
+        original_cards.copy_from_slice(hand);
+
+        let mut sorted_cards = original_cards;
+        sorted_cards.sort();
+        let mut jokers_count = 0;
+        let mut runs = 0;
+        let mut lone_cards = 0;
+        for i in 0..sorted_cards.len() {
+            jokers_count += if sorted_cards[i] == b'J' { 1 } else { 0 };
+            runs += if i == 0 || sorted_cards[i - 1] != sorted_cards[i] {
+                1
+            } else {
+                0
+            };
+            lone_cards += if i != 0 && sorted_cards[i - 1] == sorted_cards[i]
+                || i + 1 < sorted_cards.len() && sorted_cards[i + 1] == sorted_cards[i]
+            {
+                0
+            } else {
+                1
+            };
+        }
+        assert!(jokers_count > 0);
+
+        return if runs <= 2 {
+            Hand {
+                combination: CombinationType::Five,
+                cards: original_cards,
+            }
+        } else if runs == 3 && !(jokers_count == 1 && lone_cards == 1) {
+            Hand {
+                combination: CombinationType::Four,
+                cards: original_cards,
+            }
+        } else if runs == 3 && jokers_count == 1 && lone_cards == 1 {
+            Hand {
+                combination: CombinationType::FullHouse,
+                cards: original_cards,
+            }
+        } else if runs == 4 {
+            Hand {
+                combination: CombinationType::Three,
+                cards: original_cards,
+            }
+        } else {
+            Hand {
+                combination: CombinationType::OnePair,
+                cards: original_cards,
+            }
+        };
+    }
+}
+
+pub fn beats<T: GameRules>(left: &Hand, right: &Hand) -> bool {
+    if left.combination != right.combination {
+        return left.combination < right.combination;
+    }
+    for i in 0..5 {
+        if left.cards[i] != right.cards[i] {
+            return T::card_order(left.cards[i]) < T::card_order(right.cards[i]);
+        }
+    }
+    return false;
+}
+
+fn parse_bid<T: GameRules>(line: &[u8], number: usize) -> Result<(Hand, i32, &[u8]), ParseError> {
+    let end = line.iter().position(|&x| x == b'\n').unwrap_or(line.len());
+    let text = &line[..end];
+    if text.len() < 7 || text[5] != b' ' || !text[..5].iter().all(|x| SIMPLE_CARDS.contains(x)) {
+        return Err(ParseError::new(7, number, 1, text, "five cards and a bid"));
+    }
+    let mut bid = 0;
+    let hand = T::parse_hand(&line[..5]);
+
+    let mut i = 6;
+    while i < end {
+        let digit = line[i].digit();
+        if digit.is_none() {
+            return Err(ParseError::new(7, number, 7, &text[6..], "a number"));
+        }
+        bid = 10 * bid + digit.unwrap();
+        i += 1;
+    }
+    return Ok((hand, bid, &line[(i + 1).min(line.len())..]));
+}
+
+fn parse_bid_str<T: GameRules>(line: Line) -> Result<(Hand, i32), ParseError> {
+    let (hand, bid) = line.split_once(line.text, ' ')?;
+    if hand.len() != 5 || !hand.bytes().all(|x| SIMPLE_CARDS.contains(&x)) {
+        return Err(line.error(hand, "five cards"));
+    }
+    let hand = T::parse_hand(hand.as_bytes());
+
+    return Ok((hand, line.number::<i32>(bid)?));
+}
+
+// intentionally quadratic because I'm still afraid of allocations, sorry
+pub fn day7<T: GameRules>(input: &[u8]) -> Result<i32, ParseError> {
+    let mut sum = 0;
+    let mut first_line = input;
+    let mut first_number = 1;
+    while first_line.len() > 1 {
+        let (current_hand, bid, remainder) = parse_bid::<T>(first_line, first_number)?;
+        first_line = remainder;
+        first_number += 1;
+
+        let mut position = 1;
+        let mut second_line = input;
+        let mut second_number = 1;
+        while second_line.len() > 1 {
+            let (other_hand, _, remainder) = parse_bid::<T>(second_line, second_number)?;
+            second_line = remainder;
+            second_number += 1;
+            if beats::<T>(&current_hand, &other_hand) {
+                position += 1;
+            }
+        }
+        sum += position * bid;
+    }
+    return Ok(sum);
+}
+
+pub fn day7_str<T: GameRules>(input: &str) -> Result<i32, ParseError> {
+    #![allow(unused)]
+    let mut sum = 0;
+    let mut first_line = input;
+    for first in InputLines::new(7, input) {
+        let (current_hand, bid) = parse_bid_str::<T>(first)?;
+        let mut position = 1;
+        for second in InputLines::new(7, input) {
+            let (other_hand, _) = parse_bid_str::<T>(second)?;
+            if beats::<T>(&current_hand, &other_hand) {
+                position += 1;
+            }
+        }
+        sum += position * bid;
+    }
+    return Ok(sum);
+}
+
+pub struct Day7;
+impl Solution for Day7 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a [u8];
+    fn parse(input: &str) -> Result<&[u8], ParseError> {
+        return Ok(input.trim().as_bytes());
+    }
+    fn solve(input: &[u8], part: Part) -> Result<Answer, SolveError> {
+        return match part {
+            Part::Easy => Ok(day7::<SimpleGame>(input)?.into()),
+            Part::Hard => Ok(day7::<JokerGame>(input)?.into()),
+        };
+    }
+}
+
+pub struct Day7Str;
+impl Solution for Day7Str {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        return match part {
+            Part::Easy => Ok(day7_str::<SimpleGame>(input)?.into()),
+            Part::Hard => Ok(day7_str::<JokerGame>(input)?.into()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let error = day7::<SimpleGame>(b"32T3K 765\r\nT55J5 684\r\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.token.as_str()), (1, 7, "765\r"));
+    }
+
+    #[test]
+    fn variants_trim_the_input_alike() {
+        let input = "32T3K 765\nT55J5 684\n\n";
+        let bytes = Day7::parse(input).unwrap();
+        let text = Day7Str::parse(input).unwrap();
+        assert_eq!(Day7::solve(bytes, Part::Easy), Day7Str::solve(text, Part::Easy));
+        assert_eq!(Day7::solve(bytes, Part::Hard), Ok(Answer::I64(2133)));
+    }
+}
@@ -0,0 +1,523 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::combinatorics::{crt, lcm};
+use crate::parsing::{InputLines, Line, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+fn parse_node<'a>(line: Line<'a>) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+    const TRIM_CHARS: &[char] = &[' ', '(', ')'];
+    let name = |node: &'a str| {
+        let node = node.trim();
+        if node.len() == 0 || node.contains(|x: char| x.is_whitespace() || "(),=".contains(x)) {
+            return Err(line.error(node, "a node name"));
+        }
+        return Ok(node);
+    };
+    let (source, targets) = line.split_once(line.text, '=')?;
+    let (left, right) = line.split_once(targets.trim_matches(TRIM_CHARS), ',')?;
+    return Ok((name(source)?, name(left)?, name(right)?));
+}
+
+fn parse_commands<'a>(line: Line<'a>) -> Result<&'a [u8], ParseError> {
+    let commands = line.text.as_bytes();
+    if let Some(i) = commands.iter().position(|&x| x != b'L' && x != b'R') {
+        return Err(line.error(&line.text[i..i + 1], "L or R"));
+    }
+    return Ok(commands);
+}
+
+// nodes are interned into ids in the order they first appear, the tables are indexed by id
+pub struct Network<'a> {
+    pub commands: &'a [u8],
+    pub names: Vec<&'a str>,
+    pub ids: HashMap<&'a str, usize>,
+    pub transitions: Vec<(usize, usize)>,
+    // nodes ending with Z
+    pub targets: Vec<bool>,
+    // nodes in the order of their lines
+    pub nodes: Vec<usize>,
+    // nodes ending with A
+    pub ghosts: Vec<usize>,
+}
+
+impl<'a> Network<'a> {
+    pub fn id(&self, name: &str) -> Option<usize> {
+        return self.ids.get(name).copied();
+    }
+    fn intern(&mut self, name: &'a str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        self.ids.insert(name, self.names.len());
+        self.names.push(name);
+        self.transitions.push((usize::MAX, usize::MAX));
+        self.targets.push(name.ends_with('Z'));
+        return self.names.len() - 1;
+    }
+}
+
+pub fn parse_network(input: &str) -> Result<Network<'_>, ParseError> {
+    let mut lines = InputLines::new(8, input);
+    let commands = parse_commands(lines.expect("commands")?)?;
+    _ = lines.expect("an empty line")?;
+
+    let mut network = Network {
+        commands,
+        names: Vec::new(),
+        ids: HashMap::new(),
+        transitions: Vec::new(),
+        targets: Vec::new(),
+        nodes: Vec::new(),
+        ghosts: Vec::new(),
+    };
+    // the first mention of every target, to report the ones without a line
+    let mut mentions = Vec::new();
+    for line in lines {
+        let (source, left, right) = parse_node(line)?;
+        let source_id = network.intern(source);
+        if network.transitions[source_id].0 != usize::MAX {
+            return Err(line.error(source, "a node defined only once"));
+        }
+        let (left_id, right_id) = (network.intern(left), network.intern(right));
+        network.transitions[source_id] = (left_id, right_id);
+        network.nodes.push(source_id);
+        if source.ends_with('A') {
+            network.ghosts.push(source_id);
+        }
+        mentions.push((line, left, left_id));
+        mentions.push((line, right, right_id));
+    }
+    for (line, name, id) in mentions {
+        if network.transitions[id].0 == usize::MAX {
+            return Err(line.error(name, "a node defined on its own line"));
+        }
+    }
+    return Ok(network);
+}
+
+// where every node ends up after running all the commands, and the steps of that run at
+// which it stands on a ..Z node
+fn block_jumps(network: &Network) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let (commands, transitions) = (network.commands, &network.transitions);
+    let mut jumps = vec![0; transitions.len()];
+    let mut hits = vec![Vec::new(); transitions.len()];
+    for &node in &network.nodes {
+        let mut current = node;
+        for (i, command) in commands.iter().enumerate() {
+            if network.targets[current] {
+                hits[node].push(i);
+            }
+            current = if *command == b'L' {
+                transitions[current].0
+            } else {
+                transitions[current].1
+            };
+        }
+        jumps[node] = current;
+    }
+    return (jumps, hits);
+}
+
+// the steps at which a ghost stands on a ..Z node: the ones before `start`, then
+// `start + k * period + hit` for every hit of `cycle_hits`
+pub struct Ghost {
+    pub prefix_hits: Vec<i128>,
+    pub start: i128,
+    pub period: i128,
+    pub cycle_hits: Vec<i128>,
+    // the nodes starting the blocks of the cycle
+    pub cycle: Vec<usize>,
+}
+
+impl Ghost {
+    // the state only repeats at a command boundary, so the cycle is found among block starts
+    fn new(node: usize, jumps: &[usize], hits: &[Vec<usize>], commands: usize) -> Ghost {
+        let mut block_of = vec![usize::MAX; jumps.len()];
+        let mut blocks = Vec::new();
+        let mut node = node;
+        while block_of[node] == usize::MAX {
+            block_of[node] = blocks.len();
+            blocks.push(node);
+            node = jumps[node];
+        }
+        let cycle = block_of[node];
+        let steps = |blocks: &[usize]| -> Vec<i128> {
+            let mut steps = Vec::new();
+            for (k, &node) in blocks.iter().enumerate() {
+                for &hit in &hits[node] {
+                    steps.push((k * commands + hit) as i128);
+                }
+            }
+            return steps;
+        };
+        return Ghost {
+            prefix_hits: steps(&blocks[..cycle]),
+            start: (cycle * commands) as i128,
+            period: ((blocks.len() - cycle) * commands) as i128,
+            cycle_hits: steps(&blocks[cycle..]),
+            cycle: blocks[cycle..].to_vec(),
+        };
+    }
+    pub fn hits(&self, step: i128) -> bool {
+        if step < self.start {
+            return self.prefix_hits.binary_search(&step).is_ok();
+        }
+        return self.cycle_hits.binary_search(&((step - self.start) % self.period)).is_ok();
+    }
+}
+
+// the first step at which all ghosts stand on a ..Z node, None if they never do
+pub fn first_common_hit(ghosts: &[Ghost]) -> Option<i128> {
+    // before that, the ghost with the longest prefix must be in it
+    let latest = ghosts.iter().max_by_key(|ghost| ghost.start)?;
+    for &step in &latest.prefix_hits {
+        if ghosts.iter().all(|ghost| ghost.hits(step)) {
+            return Some(step);
+        }
+    }
+    // then every ghost is on its cycle, combine the residues of their hits
+    let mut residues = vec![0];
+    let mut modulus = 1;
+    for ghost in ghosts {
+        let mut combined = Vec::new();
+        for &residue in &residues {
+            for &hit in &ghost.cycle_hits {
+                let ghost_residue = (ghost.start + hit) % ghost.period;
+                combined.extend(crt(residue, modulus, ghost_residue, ghost.period));
+            }
+        }
+        combined.sort();
+        combined.dedup();
+        residues = combined;
+        modulus = lcm(modulus, ghost.period);
+    }
+    let start = latest.start;
+    return residues
+        .iter()
+        .map(|&residue| residue + (start - residue + modulus - 1) / modulus * modulus)
+        .min();
+}
+
+// Graphviz DOT of the transitions and of the jumps over the commands, with the cycles of the
+// ghosts and the commands at which they stand on ..Z nodes
+pub fn network_dot(network: &Network) -> String {
+    const COLORS: &[&str] = &["blue", "red", "darkgreen", "purple", "orange", "brown"];
+    let (jumps, hits) = block_jumps(network);
+    let commands = network.commands.len();
+    let ghosts: Vec<Ghost> = network
+        .ghosts
+        .iter()
+        .map(|&ghost| Ghost::new(ghost, &jumps, &hits, commands))
+        .collect();
+    let fill = |node: usize| match network.names[node] {
+        name if name.ends_with('A') => "palegreen",
+        name if name.ends_with('Z') => "salmon",
+        _ => "white",
+    };
+    let label = |node: usize| network.names[node].replace('"', "\\\"");
+    let quote = |prefix: &str, node: usize| format!("\"{prefix} {}\"", label(node));
+
+    let mut dot = String::new();
+    // writing to a String can't fail
+    _ = writeln!(dot, "digraph day8 {{");
+    _ = writeln!(dot, "  node [shape=circle, style=filled];");
+    _ = writeln!(dot, "  subgraph cluster_transitions {{");
+    _ = writeln!(dot, "    label=\"transitions\";");
+    for &node in &network.nodes {
+        let (name, color) = (quote("t", node), fill(node));
+        _ = writeln!(dot, "    {name} [label=\"{}\", fillcolor={color}];", label(node));
+        let (left, right) = network.transitions[node];
+        if left == right {
+            _ = writeln!(dot, "    {name} -> {} [label=\"LR\"];", quote("t", left));
+        } else {
+            _ = writeln!(dot, "    {name} -> {} [label=\"L\"];", quote("t", left));
+            _ = writeln!(dot, "    {name} -> {} [label=\"R\"];", quote("t", right));
+        }
+    }
+    _ = writeln!(dot, "  }}");
+
+    _ = writeln!(dot, "  subgraph cluster_jumps {{");
+    _ = writeln!(dot, "    label=\"jumps over {commands} commands, Z at the command offsets\";");
+    for &node in &network.nodes {
+        let (name, color) = (quote("j", node), fill(node));
+        let mut attributes = format!("label=\"{}\", fillcolor={color}", label(node));
+        if let Some(i) = network.ghosts.iter().position(|&ghost| ghost == node) {
+            let ghost = &ghosts[i];
+            let mut residues: Vec<i128> = ghost
+                .cycle_hits
+                .iter()
+                .map(|&hit| (ghost.start + hit) % ghost.period)
+                .collect();
+            residues.sort();
+            let residues: Vec<String> = residues.iter().map(|hit| hit.to_string()).collect();
+            let prefix: Vec<String> = ghost.prefix_hits.iter().map(|hit| hit.to_string()).collect();
+            _ = write!(
+                attributes,
+                ", xlabel=\"ghost {i}: cycle of {} blocks from step {}, Z at steps {} mod {}{}\"",
+                ghost.cycle.len(),
+                ghost.start,
+                residues.join(","),
+                ghost.period,
+                match prefix.len() {
+                    0 => String::new(),
+                    _ => format!(" and {} before", prefix.join(",")),
+                }
+            );
+        }
+        _ = writeln!(dot, "    {name} [{attributes}];");
+        let mut edge = Vec::new();
+        if hits[node].len() > 0 {
+            let offsets: Vec<String> = hits[node].iter().map(|hit| hit.to_string()).collect();
+            edge.push(format!("label=\"Z at {}\"", offsets.join(",")));
+        }
+        if let Some(i) = ghosts.iter().position(|ghost| ghost.cycle.contains(&node)) {
+            edge.push(format!("color={}, penwidth=2", COLORS[i % COLORS.len()]));
+        }
+        let edge = match edge.len() {
+            0 => String::new(),
+            _ => format!(" [{}]", edge.join(", ")),
+        };
+        _ = writeln!(dot, "    {name} -> {}{edge};", quote("j", jumps[node]));
+    }
+    _ = writeln!(dot, "  }}");
+    _ = writeln!(dot, "}}");
+    return dot;
+}
+
+// no solution when AAA or ZZZ is missing, or ZZZ can't be reached from AAA
+pub fn day8_easy(network: &Network) -> Result<usize, SolveError> {
+    let (commands, transitions) = (network.commands, &network.transitions);
+    let node = |name| {
+        let missing = || SolveError::NoSolution(format!("there is no node {name}"));
+        return network.id(name).ok_or_else(missing);
+    };
+    let (mut node, end) = (node("AAA")?, node("ZZZ")?);
+    let mut steps = 0;
+    while node != end {
+        // every (node, command) state was visited, it's a loop
+        if steps > network.names.len() * commands.len() {
+            return Err(SolveError::NoSolution("ZZZ can't be reached from AAA".to_string()));
+        }
+        let command = commands[steps % commands.len()];
+        node = if command == b'L' {
+            transitions[node].0
+        } else {
+            transitions[node].1
+        };
+        steps += 1;
+    }
+    return Ok(steps);
+}
+
+pub fn day8_hard(network: &Network) -> Option<i128> {
+    let (jumps, hits) = block_jumps(network);
+    let ghosts: Vec<Ghost> = network
+        .ghosts
+        .iter()
+        .map(|&ghost| Ghost::new(ghost, &jumps, &hits, network.commands.len()))
+        .collect();
+    return first_common_hit(&ghosts);
+}
+
+pub struct Day8;
+impl Solution for Day8 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Network<'a>;
+    fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+        return parse_network(input.trim());
+    }
+    fn solve(network: Network<'_>, part: Part) -> Result<Answer, SolveError> {
+        return match part {
+            Part::Easy => Ok(day8_easy(&network)?.into()),
+            Part::Hard => match day8_hard(&network) {
+                Some(steps) => Ok(steps.into()),
+                None => Err(SolveError::NoSolution(
+                    "the ghosts never all stand on a Z node at once".to_string(),
+                )),
+            },
+        };
+    }
+}
+
+// None when there are no ghosts or they never meet, which shows within the longest prefix and
+// one common period of their cycles
+pub fn day8_hard_brute_force(network: &Network) -> Option<usize> {
+    let (commands, transitions) = (network.commands, &network.transitions);
+    let mut ghosts = network.ghosts.clone();
+    if ghosts.is_empty() {
+        return None;
+    }
+    let (block_ends, hits) = block_jumps(network);
+    let cycles = ghosts.iter().map(|&ghost| Ghost::new(ghost, &block_ends, &hits, commands.len()));
+    let limit = cycles.fold((0, 1), |(start, period), ghost| {
+        return (start.max(ghost.start), lcm(period, ghost.period));
+    });
+    let limit = limit.0 + limit.1;
+
+    // one bit per command, mask_size words per node
+    let mask_size = commands.len().div_ceil(64);
+    let mut jumps = vec![0; transitions.len()];
+    let mut masks = vec![0 as u64; transitions.len() * mask_size];
+    for &node in &network.nodes {
+        let mut current = node;
+        for (i, command) in commands.iter().enumerate() {
+            if network.targets[current] {
+                masks[node * mask_size + i / 64] |= 1 << (i % 64);
+            }
+            current = if *command == b'L' {
+                transitions[current].0
+            } else {
+                transitions[current].1
+            };
+        }
+        jumps[node] = current;
+    }
+
+    let mut steps = 0;
+    {
+        'fast_loop: loop {
+            if steps as i128 > limit {
+                return None;
+            }
+            'mask_loop: for i in 0..mask_size {
+                let mut intersection = masks[ghosts[0] * mask_size + i];
+                for s in 1..ghosts.len() {
+                    intersection &= masks[ghosts[s] * mask_size + i];
+                    if intersection == 0 {
+                        continue 'mask_loop;
+                    }
+                }
+                break 'fast_loop;
+            }
+            for i in 0..ghosts.len() {
+                ghosts[i] = jumps[ghosts[i]];
+            }
+            steps += commands.len();
+        }
+        loop {
+            let mut finished = true;
+            for i in 0..ghosts.len() {
+                finished &= network.targets[ghosts[i]];
+            }
+            if finished {
+                break;
+            }
+            let command = commands[steps % commands.len()];
+            steps += 1;
+            for i in 0..ghosts.len() {
+                ghosts[i] = if command == b'L' {
+                    transitions[ghosts[i]].0
+                } else {
+                    transitions[ghosts[i]].1
+                };
+            }
+        }
+    }
+    return Some(steps);
+}
+
+pub struct Day8BruteForce;
+impl Solution for Day8BruteForce {
+    const PARTS: &'static [Part] = &[Part::Hard];
+    type Parsed<'a> = Network<'a>;
+    fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+        return parse_network(input.trim());
+    }
+    fn solve(network: Network<'_>, _part: Part) -> Result<Answer, SolveError> {
+        return match day8_hard_brute_force(&network) {
+            Some(steps) => Ok(steps.into()),
+            None => Err(SolveError::NoSolution(
+                "the ghosts never all stand on a Z node at once".to_string(),
+            )),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(network: &str, part: Part) -> Result<Answer, SolveError> {
+        return Day8::solve(parse_network(network)?, part);
+    }
+    fn solve_hard(network: &str) -> Result<Answer, SolveError> {
+        return solve(network, Part::Hard);
+    }
+
+    #[test]
+    fn missing_paths_have_no_solution() {
+        let no_solution = |reason: &str| Err(SolveError::NoSolution(reason.to_string()));
+        let network = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(solve(network, Part::Easy), no_solution("ZZZ can't be reached from AAA"));
+        let network = "L\n\nAAA = (ZZY, ZZY)\nZZY = (AAA, AAA)";
+        assert_eq!(solve(network, Part::Easy), no_solution("there is no node ZZZ"));
+        let network = "L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(solve(network, Part::Easy), no_solution("there is no node AAA"));
+        assert_eq!(solve(network, Part::Hard), Err(SolveError::NoSolution(
+            "the ghosts never all stand on a Z node at once".to_string(),
+        )));
+    }
+
+    #[test]
+    fn ghosts_meet_on_the_first_common_step() {
+        // BBA hits Z on odd steps, EEA on steps 2 mod 3 and DDA only on step 1
+        let ghosts = "L\n\nBBA = (BBZ, BBZ)\nBBZ = (BBA, BBA)\n";
+        let network = ghosts.to_string() + "EEA = (EEB, EEB)\nEEB = (EEZ, EEZ)\nEEZ = (EEA, EEA)";
+        assert_eq!(solve_hard(&network), Ok(Answer::I128(5)));
+        let network = ghosts.to_string() + "DDA = (DDZ, DDZ)\nDDZ = (DDB, DDB)\nDDB = (DDB, DDB)";
+        assert_eq!(solve_hard(&network), Ok(Answer::I128(1)));
+        // names aren't limited to three letters, nor is the number of ghosts
+        let network = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n\
+                       22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\n\
+                       XXX = (XXX, XXX)";
+        assert_eq!(solve_hard(network), Ok(Answer::I128(6)));
+        let many: Vec<String> = (0..40)
+            .map(|i| format!("g{i}A = (g{i}Z, g{i}Z)\ng{i}Z = (g{i}A, g{i}A)"))
+            .collect();
+        assert_eq!(solve_hard(&format!("{ghosts}{}", many.join("\n"))), Ok(Answer::I128(1)));
+        // CCA only hits Z on even steps
+        let network = ghosts.to_string() + "CCA = (CCB, CCB)\nCCB = (CCZ, CCZ)\nCCZ = (CCB, CCB)";
+        let error = solve_hard(&network).unwrap_err();
+        assert!(matches!(error, SolveError::NoSolution(_)));
+        let reason = "the ghosts never all stand on a Z node at once";
+        assert_eq!(error.to_string(), format!("no solution, {reason}"));
+    }
+
+    #[test]
+    fn brute_force_stops_without_a_common_step() {
+        let brute_force = |network: &str| day8_hard_brute_force(&parse_network(network).unwrap());
+        let ghosts = "L\n\nBBA = (BBZ, BBZ)\nBBZ = (BBA, BBA)\n";
+        let network = ghosts.to_string() + "EEA = (EEB, EEB)\nEEB = (EEZ, EEZ)\nEEZ = (EEA, EEA)";
+        assert_eq!(brute_force(&network), Some(5));
+        let network = ghosts.to_string() + "DDA = (DDZ, DDZ)\nDDZ = (DDB, DDB)\nDDB = (DDB, DDB)";
+        assert_eq!(brute_force(&network), Some(1));
+        let network = ghosts.to_string() + "CCA = (CCB, CCB)\nCCB = (CCZ, CCZ)\nCCZ = (CCB, CCB)";
+        assert_eq!(brute_force(&network), None);
+        assert_eq!(brute_force("L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)"), None);
+    }
+
+    #[test]
+    fn network_dot_shows_ghost_cycles() {
+        let network = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n\
+                       22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\n\
+                       XXX = (XXX, XXX)";
+        let dot = network_dot(&parse_network(network).unwrap());
+        let has = |line: &str| dot.contains(line);
+        assert!(dot.starts_with("digraph day8 {\n") && dot.ends_with("}\n"));
+        assert!(has("    \"t 11A\" -> \"t 11B\" [label=\"L\"];\n"));
+        assert!(has("    \"t 22B\" -> \"t 22C\" [label=\"LR\"];\n"));
+        assert!(has("    \"j 11Z\" [label=\"11Z\", fillcolor=salmon];\n"));
+        assert!(has("xlabel=\"ghost 0: cycle of 1 blocks from step 2, Z at steps 0 mod 2\""));
+        assert!(has("xlabel=\"ghost 1: cycle of 3 blocks from step 2, Z at steps 0,3 mod 6\""));
+        assert!(has("    \"j 22C\" -> \"j 22B\" [label=\"Z at 1\", color=red, penwidth=2];"));
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let error = parse_network("L\n\nAAA = (BBB, AAA)").err().unwrap();
+        assert_eq!((error.line, error.column, error.token.as_str()), (3, 8, "BBB"));
+        let error = parse_network("L\n\nAAA = (A A, AAA)").err().unwrap();
+        assert_eq!((error.line, error.column, error.token.as_str()), (3, 8, "A A"));
+    }
+}
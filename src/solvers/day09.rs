@@ -0,0 +1,34 @@
+use crate::combinatorics::get_c;
+use crate::parsing::{InputLines, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+pub fn day9(input: &str) -> Result<(i128, i128), ParseError> {
+    let mut forward_prediction = 0;
+    let mut backward_prediction = 0;
+    for line in InputLines::new(9, input) {
+        let count = line.text.split(' ').count();
+        for (i, number) in line.text.split(' ').rev().enumerate() {
+            let number = line.number::<i128>(number)?;
+            forward_prediction += get_c(count, i + 1) * number * (if i % 2 == 0 { 1 } else { -1 });
+        }
+        for (i, number) in line.text.split(' ').enumerate() {
+            let number = line.number::<i128>(number)?;
+            backward_prediction += get_c(count, i + 1) * number * (if i % 2 == 0 { 1 } else { -1 });
+        }
+    }
+    return Ok((forward_prediction, backward_prediction));
+}
+
+pub struct Day9;
+impl Solution for Day9 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        let (forward_prediction, backward_prediction) = day9(input)?;
+        return Ok(part.pick(forward_prediction, backward_prediction));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::geometry::{Point, DIRECTIONS_4};
+use crate::grid::Grid;
+use crate::parsing::ParseError;
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+trait GridPipes {
+    fn can_go(&self, position: Point, direction: Point) -> bool;
+}
+
+type Pipe = i32;
+const NONE: Pipe = 0b0000;
+const ALL: Pipe = 0b1111;
+const L: Pipe = 0b0001;
+const T: Pipe = 0b0010;
+const R: Pipe = 0b0100;
+const B: Pipe = 0b1000;
+const LR: Pipe = 0b0101;
+const TB: Pipe = 0b1010;
+const LT: Pipe = 0b0011;
+const LB: Pipe = 0b1001;
+const RT: Pipe = 0b0110;
+const RB: Pipe = 0b1100;
+
+fn pipe_from(symbol: u8) -> Pipe {
+    return match symbol {
+        b'S' => ALL,
+        b'|' => TB,
+        b'-' => LR,
+        b'L' => RT,
+        b'F' => RB,
+        b'J' => LT,
+        b'7' => LB,
+        _ => NONE,
+    };
+}
+
+impl GridPipes for Grid<u8> {
+    fn can_go(&self, position: Point, direction: Point) -> bool {
+        let (from, to) = (
+            pipe_from(self.at(position)),
+            pipe_from(self.at(position.add(direction))),
+        );
+        return match direction {
+            Point { x: -1, y: 0 } => from & T > 0 && to & B > 0,
+            Point { x: 1, y: 0 } => from & B > 0 && to & T > 0,
+            Point { x: 0, y: -1 } => from & L > 0 && to & R > 0,
+            Point { x: 0, y: 1 } => from & R > 0 && to & L > 0,
+            _ => panic!("unexpected symbol"),
+        };
+    }
+}
+
+#[derive(Copy, Clone)]
+struct GridCursor {
+    previous: Point,
+    current: Point,
+}
+
+trait GridWalk {
+    fn walk(&self, cursor: GridCursor) -> Option<GridCursor>;
+}
+
+impl GridWalk for Grid<u8> {
+    fn walk(&self, cursor: GridCursor) -> Option<GridCursor> {
+        for &direction in DIRECTIONS_4 {
+            if self.can_go(cursor.current, direction)
+                && cursor.current.add(direction) != cursor.previous
+            {
+                return Some(GridCursor {
+                    current: cursor.current.add(direction),
+                    previous: cursor.current,
+                });
+            }
+        }
+        return None;
+    }
+}
+
+pub fn day10(grid: &Grid<u8>) -> Result<(i32, i32), ParseError> {
+    let broken_loop = |cursor: GridCursor| {
+        let Point { x: row, y: col } = cursor.current;
+        let symbol = [grid.at(cursor.current)];
+        let (line, column) = (row as usize + 1, col as usize + 1);
+        ParseError::new(10, line, column, &symbol, "a pipe continuing the loop")
+    };
+    let start = grid.cells.iter().position(|&x| x == b'S');
+    let mut start = start
+        .and_then(|start| grid.index(start as i32))
+        .ok_or_else(|| ParseError::new(10, 1, 1, b"", "a start tile 'S'"))?;
+    let mut cursor = GridCursor {
+        current: start,
+        previous: start,
+    };
+    let mut step = 0;
+    let mut corner = cursor;
+    loop {
+        cursor = grid.walk(cursor).ok_or_else(|| broken_loop(cursor))?;
+        if cursor.current < corner.current {
+            corner = cursor;
+        }
+        step += 1;
+        if cursor.current == start {
+            break;
+        }
+    }
+
+    let mut signed_area = 0;
+    let mut delta = 0;
+    let mut corner_type = 1;
+    (cursor, start) = (corner, corner.current);
+    loop {
+        let next = grid.walk(cursor).ok_or_else(|| broken_loop(cursor))?;
+        let sign_out = next.current.y - next.previous.y;
+        signed_area += cursor.current.x * sign_out;
+        if cursor.current.sub(cursor.previous) == next.current.sub(next.previous) {
+            delta += 2;
+        } else {
+            if cursor.current != corner.current
+                && next.current.sub(next.previous) == corner.current.sub(corner.previous)
+            {
+                corner_type = 4 - corner_type;
+            }
+            delta += corner_type;
+            corner = cursor;
+        }
+
+        cursor = next;
+        if cursor.current == start {
+            break;
+        }
+    }
+    let area = signed_area.abs() - delta / 4;
+    return Ok((step / 2, area));
+}
+
+pub struct Day10;
+impl Solution for Day10 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Grid<u8>;
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        return Grid::parse(input).map_err(|error| error.into_parse_error(10));
+    }
+    fn solve(grid: Grid<u8>, part: Part) -> Result<Answer, SolveError> {
+        let (farthest, area) = day10(&grid)?;
+        return Ok(part.pick(farthest, area));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::grid::{Grid, GridView};
+use crate::parsing::ParseError;
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+// the vertical part of the distances between all pairs of galaxies, with empty rows counting
+// twice and a million times, the transposed grid gives the horizontal part
+fn row_distances(view: GridView<u8>, galaxies: usize) -> (usize, usize) {
+    let (mut distance_sum_small, mut distance_sum_large) = (0, 0);
+    let (mut top_count, mut bottom_count) = (0, galaxies);
+    for row in view.rows() {
+        let row_count = row.filter(|&&x| x == b'#').count();
+        top_count += row_count;
+        bottom_count -= row_count;
+        distance_sum_small += top_count * bottom_count * if row_count == 0 { 2 } else { 1 };
+        distance_sum_large += top_count * bottom_count * if row_count == 0 { 1000000 } else { 1 };
+    }
+    return (distance_sum_small, distance_sum_large);
+}
+
+pub fn day11(grid: &Grid<u8>) -> (usize, usize) {
+    let galaxies = grid.cells.iter().filter(|&&b| b == b'#').count();
+    let (rows_small, rows_large) = row_distances(grid.view(), galaxies);
+    let (columns_small, columns_large) = row_distances(grid.transpose(), galaxies);
+    return (rows_small + columns_small, rows_large + columns_large);
+}
+
+pub struct Day11;
+impl Solution for Day11 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Grid<u8>;
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        return Grid::parse(input).map_err(|error| error.into_parse_error(11));
+    }
+    fn solve(grid: Grid<u8>, part: Part) -> Result<Answer, SolveError> {
+        let (distance_sum_small, distance_sum_large) = day11(&grid);
+        return Ok(part.pick(distance_sum_small, distance_sum_large));
+    }
+}
@@ -0,0 +1,327 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+use crate::combinatorics::get_c;
+use crate::parsing::{InputLines, ParseError};
+use crate::solution::{Answer, Estimate, Options, Part, Solution, SolveError};
+
+struct RIter {
+    sum: i32,
+    count: i32,
+    previous_index: i32,
+    current_index: i32,
+    current_count: i32,
+}
+
+impl RIter {
+    fn new(sum: i32, count: i32) -> RIter {
+        RIter {
+            sum,
+            count,
+            previous_index: 0,
+            current_index: 1,
+            current_count: 0,
+        }
+    }
+    fn valid(&mut self, rng: &mut SmallRng) -> bool {
+        return self.current_count == self.count && self.next(rng).is_none();
+    }
+    fn next(&mut self, rng: &mut SmallRng) -> Option<i32> {
+        while self.current_index <= self.sum + self.count {
+            let chance = rng.gen_range(0..self.sum + self.count);
+            if chance >= self.count {
+                self.current_index += 1;
+                continue;
+            }
+            let result = self.current_index - self.previous_index - 1;
+            self.previous_index = self.current_index;
+            self.current_index += 1;
+            self.current_count += 1;
+            return Some(result);
+        }
+        return None;
+    }
+}
+
+fn validate_blocks(
+    rng: &mut rand::rngs::SmallRng,
+    records: &[u8],
+    blocks: &[usize],
+    distances: &mut RIter,
+) -> bool {
+    let mut valid = true;
+    let mut current = 0;
+    let mut lengths_iter = blocks.iter();
+    for i in 0..distances.count {
+        let distance = distances.next(rng);
+        if distance.is_none() {
+            return false;
+        }
+        if !valid {
+            continue;
+        }
+
+        let distance = distance.unwrap() as usize;
+        valid &= !records[current..current + distance].contains(&b'#');
+        current += distance;
+
+        let length = *lengths_iter.next().unwrap();
+        valid &= !records[current..current + length].contains(&b'.');
+        current += length;
+
+        if i < distances.count - 1 {
+            valid &= records[current] != b'#';
+            current += 1;
+        }
+    }
+    if records[current..].contains(&b'#') {
+        valid = false;
+    }
+    return valid;
+}
+
+pub struct Row<'a> {
+    pub records: &'a [u8],
+    pub blocks: Vec<usize>,
+}
+
+pub fn parse_rows(input: &str) -> Result<Vec<Row<'_>>, ParseError> {
+    let mut rows = Vec::new();
+    for line in InputLines::new(12, input) {
+        let (records, blocks) = line.split_once(line.text, ' ')?;
+        if let Some(i) = records.bytes().position(|x| !b".#?".contains(&x)) {
+            return Err(line.error(&records[i..i + 1], "'.', '#' or '?'"));
+        }
+        let mut lengths = Vec::new();
+        for block in blocks.split(',') {
+            lengths.push(line.number::<u16>(block)? as usize);
+        }
+        if lengths.iter().sum::<usize>() + lengths.len() - 1 > records.len() {
+            return Err(line.error(blocks, "blocks fitting into the records"));
+        }
+        rows.push(Row {
+            records: records.as_bytes(),
+            blocks: lengths,
+        });
+    }
+    return Ok(rows);
+}
+
+pub fn count_arrangements(records: &[u8], blocks: &[usize]) -> u128 {
+    let n = records.len();
+    let mut dots = vec![0; n + 1];
+    for i in 0..n {
+        dots[i + 1] = dots[i] + if records[i] == b'.' { 1 } else { 0 };
+    }
+    // next[i]: arrangements of the blocks after the current one in records[i..],
+    // n + 1 stands for "right after a block ending at the last record"
+    let mut next = vec![0 as u128; n + 2];
+    (next[n], next[n + 1]) = (1, 1);
+    for i in (0..n).rev() {
+        if records[i] != b'#' {
+            next[i] = next[i + 1];
+        }
+    }
+    let mut current = vec![0 as u128; n + 2];
+    for &length in blocks.iter().rev() {
+        current[n] = 0;
+        current[n + 1] = 0;
+        for i in (0..n).rev() {
+            let mut ways = if records[i] != b'#' { current[i + 1] } else { 0 };
+            let end = i + length;
+            if end <= n && dots[end] == dots[i] && (end == n || records[end] != b'#') {
+                ways += next[end + 1];
+            }
+            current[i] = ways;
+        }
+        std::mem::swap(&mut current, &mut next);
+    }
+    return next[0];
+}
+
+impl Row<'_> {
+    // records joined with '?', blocks simply repeated
+    pub fn unfold(&self, copies: usize) -> (Vec<u8>, Vec<usize>) {
+        let mut records = Vec::with_capacity((self.records.len() + 1) * copies);
+        for i in 0..copies {
+            if i > 0 {
+                records.push(b'?');
+            }
+            records.extend_from_slice(self.records);
+        }
+        return (records, self.blocks.repeat(copies));
+    }
+}
+
+pub fn day12(rows: &[Row], copies: usize) -> u128 {
+    let mut sum = 0;
+    for row in rows {
+        let (records, blocks) = row.unfold(copies);
+        sum += count_arrangements(&records, &blocks);
+    }
+    return sum;
+}
+
+const BATCH_SIZE: u64 = 1024;
+
+struct Sampling {
+    distances: i32,
+    blocks_count: i32,
+    total_count: i128,
+    valid_samples: i128,
+    total_samples: i128,
+    batches: u64,
+    done: bool,
+}
+
+impl Sampling {
+    fn new(row: &Row) -> Sampling {
+        let blocks_count = row.blocks.len() as i32;
+        let blocks_sum = row.blocks.iter().sum::<usize>() as i32;
+        let distances = row.records.len() as i32 - (blocks_count - 1) - blocks_sum;
+        let total_count = get_c((distances + blocks_count) as usize, blocks_count as usize);
+        return Sampling {
+            distances,
+            blocks_count,
+            total_count,
+            valid_samples: 0,
+            total_samples: 0,
+            batches: 0,
+            done: false,
+        };
+    }
+    fn estimate(&self) -> i128 {
+        let (valid_samples, total_samples) = (self.valid_samples, self.total_samples);
+        return (valid_samples * self.total_count + total_samples / 2) / total_samples;
+    }
+    fn variance(&self) -> f64 {
+        let p = self.valid_samples as f64 / self.total_samples as f64;
+        return p * (1.0 - p) / self.total_samples as f64 * (self.total_count as f64).powi(2);
+    }
+}
+
+// every batch has its own stream, so the estimate for a seed doesn't depend on the thread count
+fn batch_rng(seed: u64, row: usize, batch: u64) -> SmallRng {
+    let stream =
+        (row as u64).wrapping_mul(0x9e3779b97f4a7c15) ^ batch.wrapping_mul(0xbf58476d1ce4e5b9);
+    return SmallRng::seed_from_u64(seed ^ stream);
+}
+
+// valid samples out of BATCH_SIZE accepted ones
+fn sample_batch(row: &Row, sampling: &Sampling, mut rng: SmallRng) -> i128 {
+    let (mut valid_samples, mut total_samples) = (0, 0);
+    while total_samples < BATCH_SIZE {
+        let mut riter = RIter::new(sampling.distances, sampling.blocks_count);
+        let valid = validate_blocks(&mut rng, row.records, &row.blocks, &mut riter);
+        if !riter.valid(&mut rng) {
+            continue;
+        }
+        total_samples += 1;
+        if valid {
+            valid_samples += 1;
+        }
+    }
+    return valid_samples;
+}
+
+pub fn day12_sampled(rows: &[Row], precision: f64, seed: u64, threads: usize) -> Estimate {
+    let mut samplings: Vec<Sampling> = rows.iter().map(Sampling::new).collect();
+    loop {
+        // next batches of the rows still sampled, enough of them to keep every thread busy
+        let pending: Vec<usize> = (0..rows.len()).filter(|&i| !samplings[i].done).collect();
+        if pending.is_empty() {
+            break;
+        }
+        let per_row = (4 * threads).div_ceil(pending.len()) as u64;
+        let mut batches = Vec::new();
+        for &row in &pending {
+            for batch in samplings[row].batches..samplings[row].batches + per_row {
+                batches.push((row, batch));
+            }
+        }
+
+        let next = AtomicUsize::new(0);
+        let mut results = vec![0; batches.len()];
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads.min(batches.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut sampled = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            if i >= batches.len() {
+                                return sampled;
+                            }
+                            let (row, batch) = batches[i];
+                            let rng = batch_rng(seed, row, batch);
+                            sampled.push((i, sample_batch(&rows[row], &samplings[row], rng)));
+                        }
+                    })
+                })
+                .collect();
+            for worker in workers {
+                for (i, valid_samples) in worker.join().unwrap() {
+                    results[i] = valid_samples;
+                }
+            }
+        });
+
+        // merged in order, batches after the one meeting the stopping rule are dropped
+        for (&(row, _), valid_samples) in batches.iter().zip(results) {
+            let sampling = &mut samplings[row];
+            if sampling.done {
+                continue;
+            }
+            sampling.batches += 1;
+            sampling.valid_samples += valid_samples;
+            sampling.total_samples += BATCH_SIZE as i128;
+            let std_error = sampling.variance().sqrt();
+            sampling.done = sampling.total_samples > sampling.total_count && std_error < precision;
+        }
+    }
+    // rows are sampled independently, so their variances add up
+    return Estimate {
+        value: samplings.iter().map(|sampling| sampling.estimate()).sum(),
+        std_error: samplings.iter().map(|sampling| sampling.variance()).sum::<f64>().sqrt(),
+        samples: samplings.iter().map(|sampling| sampling.total_samples as u64).sum(),
+    };
+}
+
+pub struct Day12;
+impl Solution for Day12 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Vec<Row<'a>>;
+    fn parse(input: &str) -> Result<Vec<Row<'_>>, ParseError> {
+        return parse_rows(input.trim());
+    }
+    fn solve(rows: Vec<Row<'_>>, part: Part) -> Result<Answer, SolveError> {
+        let copies = match part {
+            Part::Easy => 1,
+            Part::Hard => 5,
+        };
+        return Ok(day12(&rows, copies).into());
+    }
+}
+
+// estimates the count by sampling, kept to cross-check the exact counter
+pub struct Day12Sampled;
+impl Solution for Day12Sampled {
+    const PARTS: &'static [Part] = &[Part::Easy];
+    const RANDOMIZED: bool = true;
+    type Parsed<'a> = Vec<Row<'a>>;
+    fn parse(input: &str) -> Result<Vec<Row<'_>>, ParseError> {
+        return parse_rows(input.trim());
+    }
+    fn solve(rows: Vec<Row<'_>>, part: Part) -> Result<Answer, SolveError> {
+        return Self::solve_with(rows, part, &Options::default());
+    }
+    fn solve_with(
+        rows: Vec<Row<'_>>,
+        _part: Part,
+        options: &Options,
+    ) -> Result<Answer, SolveError> {
+        let threads = options.threads.max(1);
+        return Ok(Answer::Approx(day12_sampled(&rows, 0.5, options.seed, threads)));
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::geometry::{Point, DIRECTIONS_4};
+use crate::grid::Grid;
+use crate::parsing::{ByteExtensions, ParseError};
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+// finally, we are starting alloc era!
+
+pub fn parse_heat_loss(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse(input).map_err(|error| error.into_parse_error(17))?;
+    if let Some(point) = grid.points().find(|&point| grid.at(point).digit().is_none()) {
+        let (line, column) = (point.x as usize + 1, point.y as usize + 1);
+        return Err(ParseError::new(17, line, column, &[grid.at(point)], "a digit"));
+    }
+    return Ok(grid);
+}
+
+pub fn day17_easy(grid: &Grid<u8>) -> Option<i32> {
+    let mut visited = HashMap::new();
+    let mut positions = BTreeSet::new();
+    let mut distances = HashMap::new();
+    let mut parents = HashMap::new();
+    let (left_top, right_bottom) = (Point{ x: 0, y: 0 }, Point { x: grid.height - 1, y: grid.width - 1 });
+    if left_top == right_bottom {
+        return Some(0);
+    }
+    for &direction in DIRECTIONS_4 {
+        positions.insert((0, left_top, direction));
+    }
+    while positions.len() > 0 {
+        let (mut distance, point, direction) = positions.pop_first().unwrap();
+        if visited.contains_key(&(point, direction)) {
+            continue;
+        }
+        visited.insert((point, direction), ());
+        let mut next_point = point;
+        for _ in 0..3 {
+            next_point = next_point.add(direction);
+            if let Some(cost) = grid.at(next_point).digit() { distance += cost; } else { break };
+            for &turn in DIRECTIONS_4.iter().filter(|&&x| x != direction && x.add(direction) != Point{x: 0, y: 0}) {
+                let next = distances.get(&(next_point, turn));
+                if next.is_none() || distance < *next.unwrap() {
+                    distances.insert((next_point, turn), distance);
+                    positions.insert((distance, next_point, turn));
+                    parents.insert((next_point, turn), (point, direction));
+                }
+            }
+        }
+    }
+//    let mut visual = Vec::new();
+//    for _ in 0..right_bottom.y+1 {
+//        let mut row = Vec::new();
+//        for _ in 0..right_bottom.x+1 {
+//            row.push(b'.');
+//        }
+//        visual.push(row);
+//    }
+//    let (mut current, mut d) = (right_bottom, DIRECTIONS_4[1]);
+//    let mut i = 0;
+//    let mut kek = 0;
+//    while current != left_top {
+//        dbg!(current);
+//        let previous;
+//        (previous, d) = *parents.get(&(current, d)).unwrap();
+//        while previous != current {
+//            let cell = &mut visual[current.x as usize][current.y as usize];
+//            kek += grid.at(current).digit().unwrap();
+//            if *cell == b'.' {
+//                *cell = b'0' + (i % 10);
+//            }
+//            current = current.sub(d);
+//            i += 1;
+//        }
+//    }
+//    dbg!(kek);
+//    for row in visual {
+//        eprintln!("{}", String::from_utf8(row).unwrap());
+//    }
+    // None when the crucible can't stop at the corner
+    let stops = DIRECTIONS_4.iter().filter_map(|&x| distances.get(&(right_bottom, x)));
+    return stops.min().copied();
+}
+
+pub fn day17_hard(grid: &Grid<u8>) -> Option<i32> {
+    let mut visited = HashMap::new();
+    let mut positions = BTreeSet::new();
+    let mut distances = HashMap::new();
+    let mut parents = HashMap::new();
+    let (left_top, right_bottom) = (Point{ x: 0, y: 0 }, Point { x: grid.height - 1, y: grid.width - 1 });
+    if left_top == right_bottom {
+        return Some(0);
+    }
+    for &direction in DIRECTIONS_4 {
+        positions.insert((0, left_top, direction));
+    }
+    while positions.len() > 0 {
+        let (mut distance, point, direction) = positions.pop_first().unwrap();
+        if visited.contains_key(&(point, direction)) {
+            continue;
+        }
+        visited.insert((point, direction), ());
+        let mut next_point = point;
+        for i in 0..10 {
+            next_point = next_point.add(direction);
+            if let Some(cost) = grid.at(next_point).digit() { distance += cost; } else { break };
+            if i < 3 { continue; }
+            for &turn in DIRECTIONS_4.iter().filter(|&&x| x != direction && x.add(direction) != Point{x: 0, y: 0}) {
+                let next = distances.get(&(next_point, turn));
+                if next.is_none() || distance < *next.unwrap() {
+                    distances.insert((next_point, turn), distance);
+                    positions.insert((distance, next_point, turn));
+                    parents.insert((next_point, turn), (point, direction));
+                }
+            }
+        }
+    }
+    // None when the crucible can't stop at the corner
+    let stops = DIRECTIONS_4.iter().filter_map(|&x| distances.get(&(right_bottom, x)));
+    return stops.min().copied();
+}
+
+pub struct Day17;
+impl Solution for Day17 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    type Parsed<'a> = Grid<u8>;
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        return parse_heat_loss(input);
+    }
+    fn solve(grid: Grid<u8>, part: Part) -> Result<Answer, SolveError> {
+        let best_distance = match part {
+            Part::Easy => day17_easy(&grid),
+            Part::Hard => day17_hard(&grid),
+        };
+        return match best_distance {
+            Some(distance) => Ok(distance.into()),
+            None => Err(SolveError::NoSolution(
+                "the crucible can't stop at the bottom right corner".to_string(),
+            )),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_grids_have_a_best_distance() {
+        let solve = |input: &str, part| Day17::solve(parse_heat_loss(input).unwrap(), part);
+        assert_eq!(solve("2", Part::Easy), Ok(Answer::I64(0)));
+        assert_eq!(solve("2", Part::Hard), Ok(Answer::I64(0)));
+        assert_eq!(solve("24134323", Part::Easy), Err(SolveError::NoSolution(
+            "the crucible can't stop at the bottom right corner".to_string(),
+        )));
+        assert_eq!(solve("241", Part::Easy), Ok(Answer::I64(5)));
+        assert_eq!(solve("24134323", Part::Hard), Ok(Answer::I64(20)));
+        assert_eq!(solve("24", Part::Hard), Err(SolveError::NoSolution(
+            "the crucible can't stop at the bottom right corner".to_string(),
+        )));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::geometry::{Point, DIRECTIONS_4};
+use crate::grid::Grid;
+use crate::parsing::{InputLines, Line, ParseError};
+use crate::search::reachable;
+use crate::solution::{Answer, Part, Solution, SolveError};
+
+#[derive(Debug)]
+struct Trench {
+    direction: Point,
+    distance: i32,
+}
+
+// the cells outside of the trenches, all connected through the empty border of the grid
+fn paint(grid: &Grid<i32>) -> Grid<bool> {
+    return reachable(grid, &[Point { x: 0, y: 0 }], DIRECTIONS_4, |&cell| cell == 0);
+}
+
+fn parse_distance<'a>(line: Line<'a>, token: Option<&'a str>) -> Result<i32, ParseError> {
+    let token = line.token(token, "a distance")?;
+    let distance = line.number::<i32>(token)?;
+    if distance < 1 {
+        return Err(line.error(token, "a positive distance"));
+    }
+    return Ok(distance);
+}
+
+pub fn day18_easy(input: &str) -> Result<usize, ParseError> {
+    let mut trenches = Vec::new();
+    for line in InputLines::new(18, input) {
+        let mut tokens_iter = line.text.split(' ');
+        let direction_char = line.token(tokens_iter.next(), "a direction")?;
+        let distance = parse_distance(line, tokens_iter.next())?;
+        let direction = match direction_char {
+            "U" => DIRECTIONS_4[0],
+            "R" => DIRECTIONS_4[1],
+            "D" => DIRECTIONS_4[2],
+            "L" => DIRECTIONS_4[3],
+            _ => return Err(line.error(direction_char, "U, R, D or L")),
+        };
+        trenches.push(Trench{ direction, distance });
+    }
+    let mut current_p = Point { x: 0, y: 0 };
+    let (mut min_p, mut max_p) = (current_p, current_p);
+    for trench in &trenches {
+        current_p = current_p.add(trench.direction.mult(trench.distance)); 
+        min_p.x = min_p.x.min(current_p.x);
+        min_p.y = min_p.y.min(current_p.y);
+        max_p.x = max_p.x.max(current_p.x);
+        max_p.y = max_p.y.max(current_p.y);
+    }
+    let mut grid = Grid::new(max_p.y - min_p.y + 3, max_p.x - min_p.x + 3, 0);
+    let corner = Point { x: min_p.x - 1, y: min_p.y - 1 };
+
+    current_p = Point { x: 0, y: 0 };
+    grid.set(current_p.sub(corner), 1);
+    for trench in &trenches {
+        for _ in 0..trench.distance {
+            current_p = current_p.add(trench.direction);
+            grid.set(current_p.sub(corner), 1);
+        }
+    }
+    let outside = paint(&grid);
+    return Ok(outside.cells.iter().filter(|&&x| !x).count());
+}
+
+pub fn day18_hard(input: &str) -> Result<i64, ParseError> {
+    let mut trenches = Vec::new();
+    for line in InputLines::new(18, input) {
+        let mut tokens_iter = line.text.split(' ');
+        _ = line.token(tokens_iter.next(), "a direction")?;
+        _ = parse_distance(line, tokens_iter.next())?;
+        let color = line.token(tokens_iter.next(), "a color")?;
+        let hex = color
+            .strip_prefix("(#")
+            .and_then(|color| color.strip_suffix(')'))
+            .filter(|hex| hex.len() == 6 && hex.bytes().all(|x| x.is_ascii_hexdigit()))
+            .ok_or_else(|| line.error(color, "a color like (#70c710)"))?;
+        let distance = i32::from_str_radix(&hex[..5], 16).unwrap();
+        let direction_code = i64::from_str_radix(&hex[5..], 16).unwrap();
+        if direction_code > 3 {
+            return Err(line.error(&hex[5..], "a direction code from 0 to 3"));
+        }
+        let direction = DIRECTIONS_4[((direction_code + 1) % 4) as usize];
+        trenches.push(Trench{ direction, distance });
+    }
+     let mut current_p = Point { x: 0, y: 0 };
+     let (mut xs, mut ys) = (vec![-1, 0, 1], vec![-1, 0, 1]);
+     for trench in &trenches {
+         current_p = current_p.add(trench.direction.mult(trench.distance)); 
+         for d in -1..=1 {
+             xs.push(current_p.x + d);
+             ys.push(current_p.y + d);
+         }
+     }
+     xs.sort(); xs.dedup();
+     ys.sort(); ys.dedup();
+
+     let mut grid = Grid::new(ys.len() as i32, xs.len() as i32, 0);
+     current_p = Point { x: 0, y: 0 };
+     for trench in &trenches {
+         let mut start_x = xs.binary_search(&current_p.x).unwrap() as i32;
+         let mut start_y = ys.binary_search(&current_p.y).unwrap() as i32;
+         current_p = current_p.add(trench.direction.mult(trench.distance));
+         let end_x = xs.binary_search(&current_p.x).unwrap() as i32;
+         let end_y = ys.binary_search(&current_p.y).unwrap() as i32;
+         while start_x != end_x || start_y != end_y {
+             grid.set(Point { x: start_x, y: start_y }, 1);
+             start_x += (end_x - start_x).signum();
+             start_y += (end_y - start_y).signum();
+         }
+     }
+     let outside = paint(&grid);
+     let mut area = 0;
+     for point in grid.points() {
+         if outside.at(point) {
+             continue;
+         }
+         let (x, y) = (point.x as usize, point.y as usize);
+         area += (xs[x + 1] - xs[x]) as i64 * (ys[y + 1] - ys[y]) as i64;
+     }
+     return Ok(area);
+}
+
+pub struct Day18;
+impl Solution for Day18 {
+    const PARTS: &'static [Part] = &[Part::Easy, Part::Hard];
+    const PARSES: bool = false;
+    type Parsed<'a> = &'a str;
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        return Ok(input.trim());
+    }
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        return match part {
+            Part::Easy => Ok(day18_easy(input)?.into()),
+            Part::Hard => Ok(day18_hard(input)?.into()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances_must_be_positive() {
+        for input in ["R -5 (#000000)", "R 0 (#000000)"] {
+            let error = day18_easy(input).unwrap_err();
+            assert_eq!((error.column, error.expected.as_str()), (3, "a positive distance"));
+            assert!(day18_hard(input).is_err());
+        }
+        assert_eq!(day18_easy("R 2 (#000020)\nD 1 (#000021)\nL 2 (#000022)\nU 1 (#000023)"), Ok(6));
+    }
+}
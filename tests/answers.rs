@@ -0,0 +1,127 @@
+use aoc2023::input::{Check, InputKind, InputSource, Inputs};
+use aoc2023::registry::{Solver, SOLVERS};
+use aoc2023::solution::{Answer, Options, Part};
+
+// ~10^13 steps on the real input, only the example is feasible
+const TOO_SLOW: &[(&str, InputKind)] = &[("day8_hard_brute_force", InputKind::Input)];
+
+fn check_day(day: u8) {
+    let mut inputs = Inputs::new(InputSource::Embedded).unwrap();
+    let solvers: Vec<&Solver> = SOLVERS.iter().filter(|solver| solver.day == day).collect();
+    assert!(!solvers.is_empty(), "day{day} is not registered");
+    for solver in solvers {
+        for kind in [InputKind::Example, InputKind::Input] {
+            if TOO_SLOW.contains(&(solver.variant, kind)) {
+                continue;
+            }
+            let input = inputs.load(day, kind).unwrap();
+            let input = input.unwrap_or_else(|| panic!("day{day} has no {}", kind.name()));
+            for &part in solver.parts {
+                let expected = inputs.expected(&input, part);
+                let label = format!("{} {} on {}", solver.variant, part.name(), kind.name());
+                assert!(expected.is_some(), "{label}: no recorded answer");
+                let (answer, _) = (solver.solve)(&input.text, part, &Options::default())
+                    .unwrap_or_else(|error| panic!("{label}: {error}"));
+                match Check::new(answer, expected) {
+                    Check::Fail(expected) => panic!("{label}: {answer}, expected {expected}"),
+                    // sampled answers may be slightly off, but not outside of their interval for
+                    // the fixed default seed
+                    Check::Imprecise(expected) => {
+                        let Answer::Approx(estimate) = answer else { unreachable!() };
+                        let (low, high) = estimate.interval();
+                        let value: f64 = expected.parse().unwrap();
+                        assert!(
+                            low <= value && value <= high,
+                            "{label}: {answer} in [{low:.1}, {high:.1}], expected {expected}"
+                        );
+                    }
+                    Check::Pass | Check::Unknown => {}
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn day01() {
+    check_day(1);
+}
+
+#[test]
+fn day02() {
+    check_day(2);
+}
+
+#[test]
+fn day03() {
+    check_day(3);
+}
+
+#[test]
+fn day04() {
+    check_day(4);
+}
+
+#[test]
+fn day05() {
+    check_day(5);
+}
+
+#[test]
+fn day06() {
+    check_day(6);
+}
+
+#[test]
+fn day07() {
+    check_day(7);
+}
+
+#[test]
+fn day08() {
+    check_day(8);
+}
+
+#[test]
+fn day09() {
+    check_day(9);
+}
+
+#[test]
+fn day10() {
+    check_day(10);
+}
+
+#[test]
+fn day11() {
+    check_day(11);
+}
+
+#[test]
+fn day12() {
+    check_day(12);
+}
+
+#[test]
+fn day17() {
+    check_day(17);
+}
+
+#[test]
+fn day18() {
+    check_day(18);
+}
+
+#[test]
+fn sampled_answers_only_depend_on_the_seed() {
+    let mut inputs = Inputs::new(InputSource::Embedded).unwrap();
+    let input = inputs.load(12, InputKind::Example).unwrap().unwrap();
+    let solver = SOLVERS.iter().find(|solver| solver.variant == "day12_sampled").unwrap();
+    let solve = |seed, threads| {
+        let options = Options { seed, threads };
+        let (answer, _) = (solver.solve)(&input.text, Part::Easy, &options).unwrap();
+        answer
+    };
+    assert_eq!(solve(7, 1), solve(7, 3));
+    assert_eq!(solve(7, 2), solve(7, 2));
+}